@@ -25,5 +25,5 @@ fn main() -> Result<(), std::io::Error> {
 
     Map::new()
         .map_filter(&pos, &|node: &Node| node.hl(0).is_ok())
-        .save(&Path::new("out.png"))
+        .save(Path::new("out.png"))
 }
@@ -7,7 +7,7 @@ use pathtracer::{map::gif::*, *};
 use std::env;
 
 fn main() -> std::io::Result<()> {
-    let mut gif = Gif::new("out.gif", 150, 200);
+    let mut gif = Gif::new("out.gif", 150, 200)?;
     let mut balls = Node::from_list(&[
         (50, 55),
         (70, 70),
@@ -14,5 +14,5 @@ fn main() -> std::io::Result<()> {
     for (i, node) in nodes.iter_mut().enumerate() {
         node.color = tools::seed_rgba(32 * i as u64);
     }
-    Map::new().map(&nodes).save(&Path::new("out.png"))
+    Map::new().map(&nodes).save(Path::new("out.png"))
 }
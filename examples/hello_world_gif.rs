@@ -7,7 +7,7 @@ use image::Rgba;
 use pathtracer::{map::gif::*, *};
 
 fn main() -> std::io::Result<()> {
-    let mut gif = Gif::new("out.gif", 200, 100);
+    let mut gif = Gif::new("out.gif", 200, 100)?;
     let radius = [30, 20, 40];
     let color = [[250, 20, 20, 255], [20, 20, 250, 255], [20, 250, 20, 255]];
 
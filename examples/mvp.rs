@@ -11,7 +11,7 @@ fn main() -> std::io::Result<()> {
 
     let nodes = Node::from_list(&pos);
     let nodes = Node::linked_list(nodes);
-    let net = Network::new(nodes);
+    let net = Network::new(nodes)?;
     let path = net.path("A", "E");
     let rev_path = net.path_rev("E", "A"); // It's in the game.
 
@@ -24,9 +24,12 @@ fn main() -> std::io::Result<()> {
     for (i, c) in coordinates.iter().enumerate() {
         let mut group = cluster!(c.x * spread, c.y * spread);
         group.radius(radius);
-        group.color(tools::seed_rgba((i * 23) as u64));
+        // Transparent colors are blended, so dense areas are drawn stronger.
+        let mut color = tools::seed_rgba((i * 23) as u64);
+        color.data[3] = 96;
+        group.color(color);
         group.add(children);
         groups.push(group);
     }
-    Map::new().map(&groups).save(&Path::new("out.jpg"))
+    Map::new().map(&groups).save(Path::new("out.png"))
 }
@@ -4,22 +4,54 @@ Global constants
 These constants are used to set default values for certain properties.
  */
 
-// A Node cannot have more connections than this.
-pub const MAX_LINKS: usize = 5;
-
-// Used as a hashing dividor.
-pub const NETWORK_REM: usize = 666;
-
 // Default node size (width and height).
 pub const DEFAULT_SIZE: u16 = 4;
 
 // Default node shader.
 pub const DEFAULT_SHADE: u16 = 20;
 
+// Width of the outline of Ring shaped nodes.
+pub const DEFAULT_RING_WIDTH: u16 = 2;
+
+// Width of the outline of nodes with a stroke color.
+pub const DEFAULT_STROKE_WIDTH: u16 = 1;
+
 // Node link Weight.
 pub const DEFAULT_LINK_SIZE: u16 = 2;
 
+// Default link color.
+pub const DEFAULT_LINK_RGBA: image::Rgba<u8> = image::Rgba {
+    data: [40, 40, 40, 255],
+};
+
 // Default color for everything.
 pub const DEFAULT_RGBA: image::Rgba<u8> = image::Rgba {
     data: [0, 0, 0, 255],
 };
+
+// Distance between a label and the element it belongs to.
+pub const DEFAULT_LABEL_GAP: u16 = 2;
+
+// Mean radius of the earth in metres, used for latitude and longitude.
+pub const EARTH_RADIUS: f64 = 6_371_008.8;
+
+// Furthest latitude from the equator drawn by the Web Mercator projection.
+pub const MERCATOR_MAX_LAT: f64 = 85.051_128_78;
+
+// Length of the longer side of a projected Map without a set size.
+pub const DEFAULT_PROJECTED_SIZE: u16 = 1000;
+
+// Number of steps the force-directed layout moves the elements.
+pub const DEFAULT_LAYOUT_ITERATIONS: u16 = 200;
+
+// Width and height of the area the layouts place elements in.
+pub const DEFAULT_LAYOUT_SIZE: u16 = 500;
+
+// Distance between the layers of the hierarchical layout.
+pub const DEFAULT_LAYER_GAP: u16 = 60;
+
+// Smallest distance between elements in the same layer of the hierarchical layout.
+pub const DEFAULT_NODE_GAP: u16 = 40;
+
+// Number of times the hierarchical layout reorders the layers to remove crossings.
+pub const DEFAULT_LAYOUT_SWEEPS: u16 = 8;
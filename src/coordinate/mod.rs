@@ -5,7 +5,7 @@ Extra functionality for coordinates.
 extern crate pythagoras;
 extern crate rand;
 
-use super::{tools::roll, Coordinate};
+use super::{consts, tools::roll, Coordinate, Location, Point};
 use std::{cmp::Ordering, f64};
 
 /**
@@ -16,7 +16,7 @@ Constructs a vector of generic structs from a given list convered to Coordinates
 examples/city.rs
 
 */
-pub fn from_list<T>(list: &[(i16, i16)], get: &dyn Fn(Coordinate, usize) -> T) -> Vec<T> {
+pub fn from_list<T>(list: &[(i32, i32)], get: &dyn Fn(Coordinate, usize) -> T) -> Vec<T> {
     let mut result: Vec<T> = Vec::new();
     for (i, &(x, y)) in list.iter().enumerate() {
         result.push(get(Coordinate::new(x, y), i));
@@ -29,8 +29,8 @@ Constructs a randomly positioned coordinate.
 */
 pub fn gen() -> Coordinate {
     Coordinate {
-        x: rand::random::<i16>(),
-        y: rand::random::<i16>(),
+        x: i32::from(rand::random::<i16>()),
+        y: i32::from(rand::random::<i16>()),
     }
 }
 
@@ -43,7 +43,7 @@ Calculate a coordinate based on another coordinate and a function.
 ```
 # use pathtracer::{coordinate::*, Coordinate};
 let c1 = Coordinate::new(0, 0);
-let f = |i: usize| -> Coordinate { Coordinate::new(i as i16, i as i16) };
+let f = |i: usize| -> Coordinate { Coordinate::new(i as i32, i as i32) };
 let c2 = calc(c1, 5, &f);
 assert_eq!(c2, Coordinate { x: 5, y: 5 });
 ```
@@ -72,7 +72,7 @@ assert_eq!(difference, (100, 100));
 # }
 ```
 */
-pub fn diff(c1: Coordinate, c2: Coordinate) -> (i16, i16) {
+pub fn diff(c1: Coordinate, c2: Coordinate) -> (i32, i32) {
     let c = (c1 - c2).abs();
     (c.x, c.y)
 }
@@ -97,6 +97,92 @@ pub fn distance(a: Coordinate, b: Coordinate) -> u32 {
     pythagoras::theorem(diff.0, diff.1) as u32
 }
 
+/**
+Returns the world position of a latitude and longitude in degrees, in metres east and south of where they are 0.
+
+The equirectangular projection is used, so north is up when drawn.
+
+
+## Examples
+
+```
+# use pathtracer::*;
+let p = coordinate::from_lat_lon(0.0, 1.0);
+assert_eq!(Coordinate::from(p), Coordinate::new(111_195, 0));
+let (lat, lon) = coordinate::to_lat_lon(coordinate::from_lat_lon(51.5, -0.1));
+assert!((lat - 51.5).abs() < 1e-9 && (lon + 0.1).abs() < 1e-9);
+```
+*/
+pub fn from_lat_lon(lat: f64, lon: f64) -> Point {
+    Point::new(
+        consts::EARTH_RADIUS * lon.to_radians(),
+        -consts::EARTH_RADIUS * lat.to_radians(),
+    )
+}
+
+/**
+Returns the latitude and longitude in degrees of a world position created by from_lat_lon.
+*/
+pub fn to_lat_lon(p: Point) -> (f64, f64) {
+    (
+        (-p.y / consts::EARTH_RADIUS).to_degrees(),
+        (p.x / consts::EARTH_RADIUS).to_degrees(),
+    )
+}
+
+/**
+Get the great-circle distance in metres between two latitude and longitude pairs in degrees.
+
+Derived from: https://en.wikipedia.org/wiki/Haversine_formula
+
+
+## Examples
+
+```
+# use pathtracer::coordinate::*;
+let london = (51.5074, -0.1278);
+let paris = (48.8566, 2.3522);
+assert_eq!(haversine(london, paris).round(), 343_557.0);
+```
+*/
+pub fn haversine(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (lat_a, lat_b) = (a.0.to_radians(), b.0.to_radians());
+    let d_lat = lat_b - lat_a;
+    let d_lon = (b.1 - a.1).to_radians();
+    let h = (d_lat / 2.0).sin().powi(2) + lat_a.cos() * lat_b.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * consts::EARTH_RADIUS * h.sqrt().min(1.0).asin()
+}
+
+/**
+Get the great-circle distance in metres between two world positions created by from_lat_lon.
+
+Geographic counterpart of distance, used to weigh links between Nodes created with Node::from_lat_lon.
+*/
+pub fn geo_distance(a: Point, b: Point) -> u32 {
+    haversine(to_lat_lon(a), to_lat_lon(b)).round() as u32
+}
+
+/**
+Returns the smallest area containing both areas, Each area is a pair of the min and max corner.
+
+
+## Examples
+
+```
+# use pathtracer::*;
+# use pathtracer::coordinate::*;
+let a = (Coordinate::new(0, 5), Coordinate::new(10, 10));
+let b = (Coordinate::new(5, 0), Coordinate::new(20, 8));
+assert_eq!(union(a, b), (Coordinate::new(0, 0), Coordinate::new(20, 10)));
+```
+*/
+pub fn union(a: (Coordinate, Coordinate), b: (Coordinate, Coordinate)) -> (Coordinate, Coordinate) {
+    (
+        Coordinate::new(a.0.x.min(b.0.x), a.0.y.min(b.0.y)),
+        Coordinate::new(a.1.x.max(b.1.x), a.1.y.max(b.1.y)),
+    )
+}
+
 /**
  Generate a Coordinate from a given Coordinate and randomly places it within a radius.
 
@@ -129,8 +215,8 @@ let c2 = coordinate::gen_radius(c1, 50, 100);
 ```
 */
 pub fn gen_radius(coord: Coordinate, min: u32, max: u32) -> Coordinate {
-    // Randomly gets the radius of the circle.
-    let r = f64::from(roll(min, max));
+    // Randomly gets the radius of the circle, There is nothing to roll when min and max meet.
+    let r = f64::from(if min < max { roll(min, max) } else { min });
 
     // gets a point on the circle's circumference.
     let circle = |a: f64, b: f64| a + r * b;
@@ -139,8 +225,8 @@ pub fn gen_radius(coord: Coordinate, min: u32, max: u32) -> Coordinate {
     let angle = roll(0u32, 3600u32);
     let a: f64 = f64::consts::PI * 0.001 * f64::from(angle);
 
-    let x = circle(f64::from(coord.x), a.cos()) as i16;
-    let y = circle(f64::from(coord.y), a.sin()) as i16;
+    let x = circle(f64::from(coord.x), a.cos()) as i32;
+    let y = circle(f64::from(coord.y), a.sin()) as i32;
 
     Coordinate { x, y }
 }
@@ -159,7 +245,7 @@ If deg == 0.0, no rotation occurs.
 # fn main() {
 let mut v = vec![node!(0, 100)];
 rotate_around_axis(coordinate!(), &mut v, 90.0);
-assert_eq!(v.remove(0).geo, Coordinate::new(100, 0));
+assert_eq!(v.remove(0).geo(), Coordinate::new(100, 0));
 # }
 ```
 
@@ -169,25 +255,30 @@ assert_eq!(v.remove(0).geo, Coordinate::new(100, 0));
 examples/node_plot.rs
 
 */
-pub fn rotate_around_axis(axis: Coordinate, points: &mut Vec<super::Node>, deg: f64) {
+pub fn rotate_around_axis(axis: Coordinate, points: &mut [super::Node], deg: f64) {
     if deg == 0.0 {
         return;
     }
 
+    // The world position keeps the fraction, so repeated rotations do not drift.
+    let axis = Point::from(axis);
     for p in points.iter_mut() {
-        let radius = f64::from(distance(axis, p.geo));
-        let diff = p.geo - axis;
-        let base = f64::from(diff.x).atan2(f64::from(diff.y));
-        let angle = base + (deg * f64::consts::PI / 180.0);
-
-        p.geo.y = axis.y + f64::round(angle.cos() * radius) as i16;
-        p.geo.x = axis.x + f64::round(angle.sin() * radius) as i16;
+        let world = p.world();
+        let (dx, dy) = (world.x - axis.x, world.y - axis.y);
+        let radius = dx.hypot(dy);
+        let angle = dx.atan2(dy) + (deg * f64::consts::PI / 180.0);
+
+        p.set_world(Point::new(
+            axis.x + angle.sin() * radius,
+            axis.y + angle.cos() * radius,
+        ));
     }
 }
 
 impl Ord for Coordinate {
     fn cmp(&self, other: &Coordinate) -> Ordering {
-        (self.x + self.y).cmp(&(other.x + other.y))
+        let sum = |c: &Coordinate| i64::from(c.x) + i64::from(c.y);
+        sum(self).cmp(&sum(other))
     }
 }
 
@@ -203,47 +294,82 @@ impl PartialEq for Coordinate {
     }
 }
 
+/**
+Adds the positions, Saturating at the bounds of i32 instead of overflowing.
+ */
 impl std::ops::Add for Coordinate {
     type Output = Coordinate;
 
     fn add(self, other: Coordinate) -> Coordinate {
-        Coordinate::new(self.x + other.x, self.y + other.y)
+        Coordinate::new(
+            self.x.saturating_add(other.x),
+            self.y.saturating_add(other.y),
+        )
     }
 }
 
+/**
+Subtracts the positions, Saturating at the bounds of i32 instead of overflowing.
+ */
 impl std::ops::Sub for Coordinate {
     type Output = Self;
 
     fn sub(self, other: Coordinate) -> Coordinate {
-        Coordinate::new(self.x - other.x, self.y - other.y)
+        Coordinate::new(
+            self.x.saturating_sub(other.x),
+            self.y.saturating_sub(other.y),
+        )
     }
 }
 
 impl std::ops::AddAssign for Coordinate {
     fn add_assign(&mut self, other: Coordinate) {
-        self.x += other.x;
-        self.y += other.y;
+        *self = *self + other;
     }
 }
 
 impl std::ops::SubAssign for Coordinate {
     fn sub_assign(&mut self, other: Coordinate) {
-        self.x -= other.x;
-        self.y -= other.y;
+        *self = *self - other;
     }
 }
 
+/**
+Multiplies the positions, Saturating at the bounds of i32 instead of overflowing.
+ */
 impl std::ops::Mul for Coordinate {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self {
         Coordinate {
-            x: self.x * rhs.x,
-            y: self.y * rhs.y,
+            x: self.x.saturating_mul(rhs.x),
+            y: self.y.saturating_mul(rhs.y),
         }
     }
 }
 
+/**
+Adds the positions in world space.
+ */
+impl std::ops::Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+/**
+Subtracts the positions in world space.
+ */
+impl std::ops::Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{super::Node, *};
@@ -251,7 +377,7 @@ mod tests {
     #[test]
     fn test_eq() {
         let co1: Coordinate = Coordinate::new(1, 1);
-        let co2: Coordinate = co1.clone();
+        let co2: Coordinate = co1;
         let co3: Coordinate = Coordinate::new(2, 2);
         assert_eq!(co1, co2);
         assert_ne!(co1, co3);
@@ -268,6 +394,52 @@ mod tests {
         assert!(co1 < co3);
     }
 
+    #[test]
+    fn test_haversine() {
+        assert_eq!(haversine((10.0, 20.0), (10.0, 20.0)), 0.0);
+        let quarter = haversine((0.0, 0.0), (90.0, 0.0));
+        assert!((quarter - consts::EARTH_RADIUS * f64::consts::FRAC_PI_2).abs() < 1e-6);
+        // A degree of longitude shrinks away from the equator.
+        let equator = haversine((0.0, 0.0), (0.0, 1.0));
+        let north = haversine((60.0, 0.0), (60.0, 1.0));
+        assert!((north / equator - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_geo_distance() {
+        let (a, b) = (from_lat_lon(60.0, 0.0), from_lat_lon(60.0, 1.0));
+        assert_eq!(geo_distance(a, b), 55_597);
+        assert_eq!(distance(Coordinate::from(a), Coordinate::from(b)), 111_195);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let max = coordinate!(i32::MAX);
+        let one = coordinate!(1);
+        assert_eq!(max + one, max);
+        assert_eq!(coordinate!(i32::MIN) - one, coordinate!(i32::MIN));
+        assert_eq!(max * coordinate!(2), max);
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(max.checked_sub(one), Some(coordinate!(i32::MAX - 1)));
+        assert_eq!(
+            max.checked_mul(coordinate!(-1)),
+            Some(coordinate!(-i32::MAX))
+        );
+        assert!(max > coordinate!(i32::MAX - 1, i32::MAX));
+
+        let mut c = max;
+        c += one;
+        assert_eq!(c, max);
+    }
+
+    #[test]
+    fn test_distance_far() {
+        assert_eq!(
+            distance(coordinate!(), coordinate!(300_000, 400_000)),
+            500_000
+        );
+    }
+
     #[test]
     fn test_gen_within_radius() {
         // Default
@@ -290,7 +462,7 @@ mod tests {
         for deg in 0..10 {
             let mut v = vec![Node::new("", Coordinate::new(0, 100))];
             rotate_around_axis(c1, &mut v, f64::from(deg * 360));
-            assert_eq!(v.remove(0).geo, Coordinate::new(0, 100));
+            assert_eq!(v.remove(0).geo(), Coordinate::new(0, 100));
         }
     }
 
@@ -301,7 +473,7 @@ mod tests {
         for _ in 0..4 {
             rotate_around_axis(c1, &mut v, 90.0);
         }
-        assert_eq!(v.remove(0).geo, Coordinate::new(0, 100));
+        assert_eq!(v.remove(0).geo(), Coordinate::new(0, 100));
     }
 
     #[test]
@@ -309,7 +481,7 @@ mod tests {
         let c1 = Coordinate::new(0, 0);
         let mut v = vec![Node::new("", Coordinate::new(0, 100))];
         rotate_around_axis(c1, &mut v, 90.0);
-        assert_eq!(v.remove(0).geo, Coordinate::new(100, 0));
+        assert_eq!(v.remove(0).geo(), Coordinate::new(100, 0));
     }
 
     #[test]
@@ -317,7 +489,7 @@ mod tests {
         let c1 = Coordinate::new(0, 0);
         let mut v = vec![Node::new("", Coordinate::new(0, 100))];
         rotate_around_axis(c1, &mut v, 180.0);
-        assert_eq!(v.remove(0).geo, Coordinate::new(0, -100));
+        assert_eq!(v.remove(0).geo(), Coordinate::new(0, -100));
     }
 
     #[test]
@@ -325,7 +497,19 @@ mod tests {
         let c1 = Coordinate::new(0, 0);
         let mut v = vec![Node::new("", Coordinate::new(0, 100))];
         rotate_around_axis(c1, &mut v, 270.0);
-        assert_eq!(v.remove(0).geo, Coordinate::new(-100, 0));
+        assert_eq!(v.remove(0).geo(), Coordinate::new(-100, 0));
+    }
+
+    #[test]
+    fn test_rotate_around_no_drift() {
+        let c1 = Coordinate::new(0, 0);
+        let mut v = vec![Node::new("", Coordinate::new(37, 91))];
+        for _ in 0..360 {
+            rotate_around_axis(c1, &mut v, 7.0);
+        }
+        assert_eq!(v[0].geo(), Coordinate::new(37, 91));
+        let world = v[0].world();
+        assert!((world.x - 37.0).abs() < 1e-6 && (world.y - 91.0).abs() < 1e-6);
     }
 
     #[test]
@@ -333,7 +517,7 @@ mod tests {
         let c1 = Coordinate::new(100, 100);
         let mut v = vec![Node::new("", Coordinate::new(200, 100))];
         rotate_around_axis(c1, &mut v, 90.0);
-        assert_eq!(v.remove(0).geo, Coordinate::new(100, 0));
+        assert_eq!(v.remove(0).geo(), Coordinate::new(100, 0));
     }
 
     #[test]
@@ -341,7 +525,7 @@ mod tests {
         let c1 = Coordinate::new(99, 99);
         let mut v = vec![Node::new("", Coordinate::new(199, 99))];
         rotate_around_axis(c1, &mut v, 90.0);
-        assert_eq!(v.remove(0).geo, Coordinate::new(99, -1));
+        assert_eq!(v.remove(0).geo(), Coordinate::new(99, -1));
     }
 
     #[test]
@@ -382,6 +566,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_clone() {
         let co1: Coordinate = Coordinate::new(1, 1);
         let co2: Coordinate = Coordinate::new(9999, 9999);
@@ -390,6 +575,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_clone_macros() {
         let co1: Coordinate = coordinate!();
         let co2: Coordinate = coordinate!(400);
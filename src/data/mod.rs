@@ -2,12 +2,12 @@
 Converts strings to mapped Nodes and Groups.
 */
 
-use super::{consts, coordinate, tools, Coordinate, Group};
+use super::{coordinate, error, tools, Coordinate, Group, Placement};
 use std::{
-    collections::hash_map::DefaultHasher,
+    collections::{hash_map::DefaultHasher, HashSet},
     fs::OpenOptions,
     hash::{Hash, Hasher},
-    io::{self, prelude::*},
+    io::prelude::*,
 };
 
 /**
@@ -24,7 +24,7 @@ pub struct CustomConverter<'a> {
 /**
 Reads from the provided file, and converts to a path network using default settings.
 */
-pub fn convert_file(path: &str, lambda: &dyn Fn(&str) -> bool) -> Result<Vec<Group>, io::Error> {
+pub fn convert_file(path: &str, lambda: &dyn Fn(&str) -> bool) -> error::Result<Vec<Group>> {
     let content = content(path)?;
     Ok(convert(&content, &lambda))
 }
@@ -32,7 +32,7 @@ pub fn convert_file(path: &str, lambda: &dyn Fn(&str) -> bool) -> Result<Vec<Gro
 /**
 Reads from the provided file, and returns content.
 */
-fn content(path: &str) -> Result<String, io::Error> {
+fn content(path: &str) -> error::Result<String> {
     let mut file = OpenOptions::new().read(true).open(path)?;
 
     let mut contents = String::new();
@@ -45,7 +45,7 @@ Initializes a CustomConverter a converts the content to a vector of groups and l
 */
 pub fn convert(content: &str, lambda: &dyn Fn(&str) -> bool) -> Vec<Group> {
     let cct = CustomConverter::new('\n', 30, 120, &lambda);
-    convert_inner(&content, &cct).unwrap()
+    convert_inner(content, &cct).unwrap()
 }
 
 impl<'a> CustomConverter<'a> {
@@ -57,7 +57,7 @@ impl<'a> CustomConverter<'a> {
         node_range: u32,
         radius: u32,
         lambda_tag: &'a dyn Fn(&str) -> bool,
-    ) -> CustomConverter {
+    ) -> CustomConverter<'a> {
         CustomConverter {
             split,
             node_range,
@@ -71,20 +71,18 @@ impl<'a> CustomConverter<'a> {
 /**
 Constructs a vector of groups and links using a CustomConverter and the string to analyze.
 */
-pub fn convert_inner(content: &str, cct: &CustomConverter) -> io::Result<Vec<Group>> {
-    let mut gr_bool_arr: [bool; consts::NETWORK_REM] = [false; consts::NETWORK_REM];
+pub fn convert_inner(content: &str, cct: &CustomConverter) -> error::Result<Vec<Group>> {
+    let mut seen = HashSet::new();
 
     let lines = content
         .split(cct.split)
         .filter(|x| !x.is_empty() && (cct.lambda_tag)(x))
         .collect::<Vec<_>>();
 
-    let lines = lines.iter().fold(vec![], |acc, hash| {
-        let hash = calculate_hash(hash);
-        let pos = (hash % consts::NETWORK_REM as u64) as usize;
-        if !gr_bool_arr[pos] {
-            gr_bool_arr[pos] = true;
-            push_group(acc, hash)
+    let lines = lines.iter().fold(vec![], |acc, line| {
+        let hash = calculate_hash(line);
+        if seen.insert(hash) {
+            push_group(acc, hash, line.trim())
         } else {
             push_node(acc, hash)
         }
@@ -92,9 +90,10 @@ pub fn convert_inner(content: &str, cct: &CustomConverter) -> io::Result<Vec<Gro
     Ok(lines)
 }
 
-fn push_group(mut groups: Vec<Group>, hash: u64) -> Vec<Group> {
+fn push_group(mut groups: Vec<Group>, hash: u64, label: &str) -> Vec<Group> {
     let mut group = Group::new("", coordinate::gen_radius(Coordinate::new(1, 0), 0, 100));
     group.settings.hash = hash;
+    group.set_label(label, Placement::Above);
     group.settings.color = tools::seed_rgba(hash);
     group.new_node_min_max(groups.len() as u32, 40);
     if !groups.is_empty() {
@@ -107,7 +106,7 @@ fn push_group(mut groups: Vec<Group>, hash: u64) -> Vec<Group> {
 fn push_node(mut groups: Vec<Group>, hash: u64) -> Vec<Group> {
     let index = groups
         .iter()
-        .position(|ref g| g.settings.hash == hash)
+        .position(|g| g.settings.hash == hash)
         .expect("Group located, but no hash matching.");
     groups[index].new_node_min_max(index as u32, 40);
     groups
@@ -159,16 +158,35 @@ mod tests {
         eval_result(res);
     }
 
+    #[test]
+    fn test_convert_many_lines() {
+        // More distinct lines than the old fixed-size table had room for.
+        let content = (0..2000)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let res = convert(&content, &|_x| true);
+        assert_eq!(res.len(), 2000);
+        assert!(res.iter().all(|g| g.nodes.len() == 1));
+    }
+
+    #[test]
+    fn test_convert_labels() {
+        let res = convert("a\nb\na\n", &|_x| true);
+        let labels = res.iter().map(|g| g.label()).collect::<Vec<_>>();
+        assert_eq!(labels, vec![Some("a"), Some("b")]);
+    }
+
     #[test]
     fn test_convert_file() {
         let path = Path::new("test.txt");
-        let mut file = File::create(&path).unwrap();
+        let mut file = File::create(path).unwrap();
 
         let content = "a\nb\nc\na\nb\nc\nb\nb\nb\nc";
-        let _ = file.write_all(content.as_bytes()).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
         let res = convert_file("test.txt", &|_x| true).unwrap();
         eval_result(res);
-        let _ = fs::remove_file("test.txt").unwrap();
+        fs::remove_file("test.txt").unwrap();
     }
 
     #[test]
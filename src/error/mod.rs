@@ -0,0 +1,124 @@
+/*!
+Errors returned by the crate.
+
+Every fallible function returns the Error enum, so callers can tell apart why something failed.
+*/
+
+use std::{fmt, io};
+
+/**
+Shorthand for results using the crate Error.
+*/
+pub type Result<T> = std::result::Result<T, Error>;
+
+/**
+All errors that can occur in the crate.
+
+
+## Io
+
+Reading or writing a file failed.
+
+
+## NotInNetwork
+
+The named element does not exist in the Network.
+
+
+## Duplicate
+
+An element with the same hash already exists in the Network.
+
+
+## NoPath
+
+No path connects the start and goal.
+
+
+## LinkIndex
+
+A link was requested at an index the Node does not have.
+
+
+## Parse
+
+A line of content could not be parsed, Lines are counted from 1.
+
+*/
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    NotInNetwork(String),
+    Duplicate(u64),
+    NoPath,
+    LinkIndex { index: usize, len: usize },
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::NotInNetwork(name) => write!(f, "{} does not exist in Network", name),
+            Error::Duplicate(hash) => write!(f, "element {} already exists in Network", hash),
+            Error::NoPath => write!(f, "not a valid path"),
+            Error::LinkIndex { index, len } => write!(
+                f,
+                "link index {} too large or not connected, Node has {} links",
+                index, len
+            ),
+            Error::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/**
+Allows the ? operator in functions returning std::io::Result.
+*/
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        let kind = match e {
+            Error::Io(e) => return e,
+            Error::NotInNetwork(_) => io::ErrorKind::NotFound,
+            Error::Duplicate(_) => io::ErrorKind::AlreadyExists,
+            Error::Parse { .. } => io::ErrorKind::InvalidData,
+            _ => io::ErrorKind::Other,
+        };
+        io::Error::new(kind, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn io_round_trip() {
+        let e = Error::from(io::Error::new(io::ErrorKind::NotFound, "missing"));
+        assert!(matches!(e, Error::Io(_)));
+        assert_eq!(io::Error::from(e).kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn io_kinds() {
+        let e = io::Error::from(Error::Duplicate(5));
+        assert_eq!(e.kind(), io::ErrorKind::AlreadyExists);
+        let e = io::Error::from(Error::NoPath);
+        assert_eq!(e.kind(), io::ErrorKind::Other);
+    }
+}
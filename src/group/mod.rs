@@ -28,7 +28,7 @@ pub fn count(list: &[Group]) -> usize {
     list.iter().fold(0, |acc, x| acc + x.nodes.len())
 }
 
-impl<'a> PartialEq for Group {
+impl PartialEq for Group {
     fn eq(&self, other: &Group) -> bool {
         self.hash() == other.hash()
     }
@@ -58,7 +58,7 @@ pub fn add_node(group: &mut Group, name: Option<&str>, min: Option<u32>, max: Op
     let mi = cmp::min(min, max);
     let ma = cmp::max(min, max);
 
-    let geo = coordinate::gen_radius(group.settings.geo, mi, ma);
+    let geo = coordinate::gen_radius(group.settings.geo(), mi, ma);
     let mut node = Node::new(name, geo);
     node.color = group.gen_color(geo);
     node.radius = group.settings.radius;
@@ -0,0 +1,912 @@
+/*!
+Positions linked Nodes and Groups automatically, instead of placing them by hand.
+
+The layouts only move the elements, their links are kept, and their ends are moved along with them.
+ */
+
+use super::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::{
+    cmp,
+    collections::{HashMap, HashSet, VecDeque},
+};
+
+/**
+Settings for the force-directed layout, See force_directed.
+
+Elements are placed randomly in the area between min and max, and then moved for a number of iterations.
+Every element pushes the others away by the repulsion, while links pull the elements they connect together.
+The same seed always gives the same layout, The starting positions are drawn with ChaCha8,
+which gives the same numbers on every platform and version of rand.
+ */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ForceDirected {
+    iterations: u32,
+    repulsion: f64,
+    min: Coordinate,
+    max: Coordinate,
+    seed: u64,
+}
+
+impl ForceDirected {
+    /**
+    Constructs the settings for placing elements in the area between min and max.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::{layout::ForceDirected, *};
+    let settings = ForceDirected::new(Coordinate::new(0, 0), Coordinate::new(200, 100))
+        .iterations(50)
+        .repulsion(2.0)
+        .seed(7);
+    ```
+     */
+    pub fn new(min: Coordinate, max: Coordinate) -> Self {
+        ForceDirected {
+            iterations: u32::from(consts::DEFAULT_LAYOUT_ITERATIONS),
+            repulsion: 1.0,
+            min,
+            max,
+            seed: 0,
+        }
+    }
+
+    /**
+    Sets the number of times the elements are moved, More iterations give a calmer layout.
+     */
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /**
+    Sets how strongly the elements push each other away, 1.0 by default.
+     */
+    pub fn repulsion(mut self, repulsion: f64) -> Self {
+        self.repulsion = repulsion;
+        self
+    }
+
+    /**
+    Sets the seed of the random starting positions.
+     */
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl Default for ForceDirected {
+    fn default() -> Self {
+        let size = i32::from(consts::DEFAULT_LAYOUT_SIZE);
+        ForceDirected::new(coordinate!(), coordinate!(size))
+    }
+}
+
+/**
+Places the elements using a force-directed algorithm, and returns them.
+
+Links are treated as undirected, Links to elements outside of the list do not move anything.
+
+Derived from: https://en.wikipedia.org/wiki/Force-directed_graph_drawing
+
+
+## Examples
+
+```
+# use pathtracer::{layout::ForceDirected, *};
+let nodes = Node::linked_list(Node::from_list(&[(0, 0); 4]));
+let settings = ForceDirected::new(Coordinate::new(0, 0), Coordinate::new(100, 100));
+let nodes = layout::force_directed(nodes, &settings);
+
+assert!(nodes.iter().all(|node| node.x() <= 100 && node.y() <= 100));
+
+// The same seed always gives the same positions.
+let again = layout::force_directed(nodes.clone(), &settings);
+assert!(nodes.iter().zip(again).all(|(a, b)| a.geo() == b.geo()));
+```
+
+Groups are moved as a whole, pulled by the links of the Nodes inside of them.
+
+```
+# use pathtracer::{layout::ForceDirected, *};
+let groups = data::convert("a\nb\nc\na\nb\nd", &|_| true);
+let groups = layout::force_directed(groups, &ForceDirected::default());
+let image = Map::new().map(&groups).consume();
+```
+ */
+pub fn force_directed<T: Layout>(mut elements: Vec<T>, settings: &ForceDirected) -> Vec<T> {
+    let n = elements.len();
+    if n == 0 {
+        return elements;
+    }
+
+    let (min, max) = (Point::from(settings.min), Point::from(settings.max));
+    let (width, height) = ((max.x - min.x).max(1.0), (max.y - min.y).max(1.0));
+    let mut rng = ChaCha8Rng::seed_from_u64(settings.seed);
+    let mut positions = (0..n)
+        .map(|_| {
+            Point::new(
+                min.x + rng.gen::<f64>() * width,
+                min.y + rng.gen::<f64>() * height,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // The ideal distance between elements, Evenly filling the area.
+    let k = (width * height / n as f64).sqrt();
+    let edges = edges(&elements);
+    let mut temperature = width.max(height) / 10.0;
+    let cooling = temperature / f64::from(settings.iterations.max(1));
+
+    for _ in 0..settings.iterations {
+        let mut moves = vec![Point::new(0.0, 0.0); n];
+        for i in 0..n {
+            for j in i + 1..n {
+                let (x, y, d) = delta(positions[i], positions[j]);
+                let force = settings.repulsion * k * k / d / d;
+                moves[i].x += x * force;
+                moves[i].y += y * force;
+                moves[j].x -= x * force;
+                moves[j].y -= y * force;
+            }
+        }
+        for &(i, j) in &edges {
+            let (x, y, d) = delta(positions[i], positions[j]);
+            let force = d / k;
+            moves[i].x -= x * force;
+            moves[i].y -= y * force;
+            moves[j].x += x * force;
+            moves[j].y += y * force;
+        }
+
+        // Elements move at most as far as the temperature, which cools down every iteration.
+        for (position, step) in positions.iter_mut().zip(moves) {
+            let length = step.x.hypot(step.y);
+            if length > 0.0 {
+                let scale = length.min(temperature) / length;
+                position.x = (position.x + step.x * scale).clamp(min.x, max.x);
+                position.y = (position.y + step.y * scale).clamp(min.y, max.y);
+            }
+        }
+        temperature -= cooling;
+    }
+
+    for (element, position) in elements.iter_mut().zip(positions) {
+        element.place(position);
+    }
+    relink_all(&mut elements);
+    elements
+}
+
+/**
+Moves the ends of the links to the positions of what they point at, offset is added to the to end.
+
+The via positions are dropped, as they were placed for the old positions.
+ */
+pub fn relink(links: &mut [HL], from: Point, ends: &HashMap<u64, Point>, offset: Point) {
+    for link in links {
+        link.from = Some(from);
+        link.via.clear();
+        if let Some(&to) = ends.get(&link.t) {
+            link.to = Some(to + offset);
+        }
+    }
+}
+
+/**
+Sets the via positions of the links pointing at any of the hashes, offset is added to every position.
+ */
+pub fn route(links: &mut [HL], to: &[u64], via: &[Point], offset: Point) {
+    for link in links.iter_mut().filter(|link| to.contains(&link.t)) {
+        link.via = via.iter().map(|&p| p + offset).collect();
+    }
+}
+
+/**
+Relinks every element to the current positions of the others.
+ */
+fn relink_all<T: Layout>(elements: &mut [T]) {
+    let ends = elements
+        .iter()
+        .flat_map(Layout::ends)
+        .collect::<HashMap<_, _>>();
+    for element in elements.iter_mut() {
+        element.relink(&ends);
+    }
+}
+
+/**
+Returns the pairs of indexes of linked elements, ordered and without duplicates.
+ */
+fn edges<T: Layout>(elements: &[T]) -> Vec<(usize, usize)> {
+    let mut edges = links(elements)
+        .into_iter()
+        .map(|(i, j)| (i.min(j), i.max(j)))
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+    edges.dedup();
+    edges
+}
+
+/**
+Returns the pairs of indexes of elements and the elements they link to, without duplicates or links to themselves.
+ */
+fn links<T: Layout>(elements: &[T]) -> Vec<(usize, usize)> {
+    let owners = elements
+        .iter()
+        .enumerate()
+        .flat_map(|(i, element)| element.ends().into_iter().map(move |(hash, _)| (hash, i)))
+        .collect::<HashMap<_, _>>();
+
+    let mut links = elements
+        .iter()
+        .enumerate()
+        .flat_map(|(i, element)| {
+            element
+                .targets()
+                .into_iter()
+                .filter_map(|t| owners.get(&t))
+                .filter(move |&&j| j != i)
+                .map(move |&j| (i, j))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    links.sort_unstable();
+    links.dedup();
+    links
+}
+
+/**
+Returns the difference from b to a and its length, Elements on top of each other are pushed apart sideways.
+ */
+fn delta(a: Point, b: Point) -> (f64, f64, f64) {
+    let (x, y) = (a.x - b.x, a.y - b.y);
+    let d = x.hypot(y);
+    if d < 0.01 {
+        (0.01, 0.0, 0.01)
+    } else {
+        (x, y, d)
+    }
+}
+
+/**
+Settings for the hierarchical layout, See hierarchical.
+
+The first layer is placed at origin, and every following layer layer_gap further down.
+Elements in the same layer are at least node_gap apart.
+The sweeps are the number of times the layers are reordered to remove crossings, and the elements moved closer to what they are linked with.
+ */
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Hierarchical {
+    origin: Coordinate,
+    layer_gap: u32,
+    node_gap: u32,
+    sweeps: u32,
+}
+
+impl Hierarchical {
+    /**
+    Constructs the settings for placing the layers with the top-left corner at origin.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::{layout::Hierarchical, *};
+    let settings = Hierarchical::new(Coordinate::new(0, 0))
+        .layer_gap(100)
+        .node_gap(20)
+        .sweeps(12);
+    ```
+     */
+    pub fn new(origin: Coordinate) -> Self {
+        Hierarchical {
+            origin,
+            layer_gap: u32::from(consts::DEFAULT_LAYER_GAP),
+            node_gap: u32::from(consts::DEFAULT_NODE_GAP),
+            sweeps: u32::from(consts::DEFAULT_LAYOUT_SWEEPS),
+        }
+    }
+
+    /**
+    Sets the distance between the layers.
+     */
+    pub fn layer_gap(mut self, gap: u32) -> Self {
+        self.layer_gap = gap;
+        self
+    }
+
+    /**
+    Sets the smallest distance between elements in the same layer.
+     */
+    pub fn node_gap(mut self, gap: u32) -> Self {
+        self.node_gap = gap;
+        self
+    }
+
+    /**
+    Sets the number of times the layers are reordered and aligned.
+     */
+    pub fn sweeps(mut self, sweeps: u32) -> Self {
+        self.sweeps = sweeps;
+        self
+    }
+}
+
+impl Default for Hierarchical {
+    fn default() -> Self {
+        Hierarchical::new(coordinate!())
+    }
+}
+
+/**
+Places the elements in layers, so links point down to a later layer, and returns them.
+
+Elements nothing links to are placed in the first layer, and every other element one layer
+below the furthest element linking to it. Links that form a cycle are turned around when placing.
+Links spanning several layers pass through virtual elements in between, which are kept out of each others way.
+The links bend at the positions of the virtual elements, See HL::via.
+
+The layers are reordered to reduce the number of crossing links, and the elements are moved to line up with what they are linked with.
+Drawing the links with EdgeStyle::Straight gives the look of a dependency graph.
+
+Derived from: https://en.wikipedia.org/wiki/Layered_graph_drawing
+
+
+## Examples
+
+```
+# use pathtracer::{layout::Hierarchical, *};
+# fn main() -> Result<(), Error> {
+let mut nodes = Node::from_list(&[(0, 0); 4]);
+let copy = nodes.clone();
+nodes[0].link(&copy[1]);
+nodes[0].link(&copy[2]);
+nodes[1].link(&copy[3]);
+nodes[2].link(&copy[3]);
+for node in nodes.iter_mut() {
+    for i in 0..node.links().len() {
+        node.hl_mut(i)?.style(EdgeStyle::Straight);
+    }
+}
+
+let nodes = layout::hierarchical(nodes, &Hierarchical::default());
+let ranks = nodes.iter().map(|node| node.y()).collect::<Vec<_>>();
+assert_eq!(ranks, vec![0, 60, 60, 120]);
+
+let image = Map::new().map(&nodes).consume();
+# Ok(())
+# }
+```
+ */
+pub fn hierarchical<T: Layout>(mut elements: Vec<T>, settings: &Hierarchical) -> Vec<T> {
+    let n = elements.len();
+    if n == 0 {
+        return elements;
+    }
+    let original = links(&elements);
+    let links = acyclic(n, &original);
+    let mut ranks = ranks(n, &links);
+
+    // Links spanning several layers are split up with virtual elements, placed after the real ones.
+    let mut up = vec![vec![]; n];
+    let mut down = vec![vec![]; n];
+    let mut chains = Vec::new();
+    for &(from, to) in &links {
+        let mut prev = from;
+        let mut chain = Vec::new();
+        for rank in ranks[from] + 1..ranks[to] {
+            let virtual_element = ranks.len();
+            ranks.push(rank);
+            up.push(vec![prev]);
+            down.push(vec![]);
+            down[prev].push(virtual_element);
+            chain.push(virtual_element);
+            prev = virtual_element;
+        }
+        down[prev].push(to);
+        up[to].push(prev);
+        chains.push((from, to, chain));
+    }
+
+    let mut layers = vec![vec![]; ranks.iter().max().map_or(0, |max| max + 1)];
+    for (element, &rank) in ranks.iter().enumerate() {
+        layers[rank].push(element);
+    }
+    let layers = order(layers, &up, &down, settings.sweeps);
+    let columns = columns(&layers, &up, &down, settings);
+
+    let origin = Point::from(settings.origin);
+    let gap = f64::from(settings.layer_gap);
+    let position = |i: usize| Point::new(origin.x + columns[i], origin.y + ranks[i] as f64 * gap);
+    for (i, element) in elements.iter_mut().enumerate() {
+        element.place(position(i));
+    }
+    relink_all(&mut elements);
+
+    // Links turned around to break a cycle pass through the virtual elements backwards.
+    for (from, to, chain) in chains.into_iter().filter(|(_, _, chain)| !chain.is_empty()) {
+        let mut via = chain.into_iter().map(position).collect::<Vec<_>>();
+        for &(from, to) in &[(from, to), (to, from)] {
+            if original.binary_search(&(from, to)).is_ok() {
+                let hashes = elements[to]
+                    .ends()
+                    .into_iter()
+                    .map(|(hash, _)| hash)
+                    .collect::<Vec<_>>();
+                elements[from].route(&hashes, &via);
+            }
+            via.reverse();
+        }
+    }
+    elements
+}
+
+/**
+Turns around the links that close a cycle, found with a depth-first search in the order of the elements.
+ */
+fn acyclic(n: usize, links: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut targets = vec![vec![]; n];
+    for &(from, to) in links {
+        targets[from].push(to);
+    }
+
+    // Unvisited, being visited and visited.
+    let mut state = vec![0u8; n];
+    let mut back = HashSet::new();
+    for start in 0..n {
+        if state[start] != 0 {
+            continue;
+        }
+        state[start] = 1;
+        let mut stack = vec![(start, 0)];
+        while let Some((from, i)) = stack.pop() {
+            match targets[from].get(i) {
+                Some(&to) => {
+                    stack.push((from, i + 1));
+                    match state[to] {
+                        0 => {
+                            state[to] = 1;
+                            stack.push((to, 0));
+                        }
+                        1 => {
+                            back.insert((from, to));
+                        }
+                        _ => {}
+                    }
+                }
+                None => state[from] = 2,
+            }
+        }
+    }
+
+    let mut links = links
+        .iter()
+        .map(|&(from, to)| {
+            if back.contains(&(from, to)) {
+                (to, from)
+            } else {
+                (from, to)
+            }
+        })
+        .collect::<Vec<_>>();
+    links.sort_unstable();
+    links.dedup();
+    links
+}
+
+/**
+Returns the layer of every element, The length of the longest path of links leading to it.
+ */
+fn ranks(n: usize, links: &[(usize, usize)]) -> Vec<usize> {
+    let mut targets = vec![vec![]; n];
+    let mut incoming = vec![0; n];
+    for &(from, to) in links {
+        targets[from].push(to);
+        incoming[to] += 1;
+    }
+
+    let mut ranks = vec![0; n];
+    let mut queue = (0..n)
+        .filter(|&i| incoming[i] == 0)
+        .collect::<VecDeque<_>>();
+    while let Some(from) = queue.pop_front() {
+        for &to in &targets[from] {
+            ranks[to] = cmp::max(ranks[to], ranks[from] + 1);
+            incoming[to] -= 1;
+            if incoming[to] == 0 {
+                queue.push_back(to);
+            }
+        }
+    }
+    ranks
+}
+
+/**
+Returns the index of every element in its layer.
+ */
+fn indexes(layers: &[Vec<usize>], total: usize) -> Vec<usize> {
+    let mut indexes = vec![0; total];
+    for layer in layers {
+        for (i, &element) in layer.iter().enumerate() {
+            indexes[element] = i;
+        }
+    }
+    indexes
+}
+
+/**
+Counts the number of links crossing each other between the layers.
+ */
+fn crossings(layers: &[Vec<usize>], down: &[Vec<usize>]) -> usize {
+    let indexes = indexes(layers, down.len());
+    layers
+        .iter()
+        .map(|layer| {
+            let links = layer
+                .iter()
+                .flat_map(|&from| down[from].iter().map(move |&to| (from, to)))
+                .map(|(from, to)| (indexes[from], indexes[to]))
+                .collect::<Vec<_>>();
+            links
+                .iter()
+                .enumerate()
+                .flat_map(|(i, a)| links[i + 1..].iter().map(move |b| (a, b)))
+                .filter(|(a, b)| (a.0 < b.0 && a.1 > b.1) || (a.0 > b.0 && a.1 < b.1))
+                .count()
+        })
+        .sum()
+}
+
+/**
+Reorders the layers by the average index of the elements they are linked with, Sweeping down and up in turns.
+
+Returns the order with the least crossings found.
+ */
+fn order(
+    mut layers: Vec<Vec<usize>>,
+    up: &[Vec<usize>],
+    down: &[Vec<usize>],
+    sweeps: u32,
+) -> Vec<Vec<usize>> {
+    let mut best = (crossings(&layers, down), layers.clone());
+    for sweep in 0..sweeps {
+        if best.0 == 0 {
+            break;
+        }
+        let len = layers.len();
+        if sweep % 2 == 0 {
+            for layer in 1..len {
+                reorder(&mut layers, layer, up);
+            }
+        } else {
+            for layer in (0..len - 1).rev() {
+                reorder(&mut layers, layer, down);
+            }
+        }
+        let crossings = crossings(&layers, down);
+        if crossings < best.0 {
+            best = (crossings, layers.clone());
+        }
+    }
+    best.1
+}
+
+/**
+Sorts a layer by the average index of the linked elements, Elements without links keep their index.
+ */
+fn reorder(layers: &mut [Vec<usize>], layer: usize, linked: &[Vec<usize>]) {
+    let indexes = indexes(layers, linked.len());
+    let mut keyed = layers[layer]
+        .iter()
+        .enumerate()
+        .map(|(i, &element)| {
+            let key = average(linked[element].iter().map(|&other| indexes[other] as f64))
+                .unwrap_or(i as f64);
+            (key, element)
+        })
+        .collect::<Vec<_>>();
+    keyed.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(cmp::Ordering::Equal));
+    layers[layer] = keyed.into_iter().map(|(_, element)| element).collect();
+}
+
+/**
+Returns the x position of every element relative to the leftmost one.
+
+Elements start evenly spaced, and are moved towards the average position of what they are linked with in turns.
+ */
+fn columns(
+    layers: &[Vec<usize>],
+    up: &[Vec<usize>],
+    down: &[Vec<usize>],
+    settings: &Hierarchical,
+) -> Vec<f64> {
+    let gap = f64::from(settings.node_gap);
+    let mut columns = indexes(layers, up.len())
+        .into_iter()
+        .map(|i| i as f64 * gap)
+        .collect::<Vec<_>>();
+
+    for sweep in 0..settings.sweeps {
+        let (order, linked) = if sweep % 2 == 0 {
+            (layers.iter().collect::<Vec<_>>(), up)
+        } else {
+            (layers.iter().rev().collect::<Vec<_>>(), down)
+        };
+        for layer in order {
+            let wanted = layer
+                .iter()
+                .map(|&element| {
+                    average(linked[element].iter().map(|&other| columns[other]))
+                        .unwrap_or(columns[element])
+                })
+                .collect::<Vec<_>>();
+            for (&element, x) in layer.iter().zip(spread(&wanted, gap)) {
+                columns[element] = x;
+            }
+        }
+    }
+
+    let min = columns.iter().cloned().fold(f64::INFINITY, f64::min);
+    columns.iter().map(|x| x - min).collect()
+}
+
+/**
+Moves the wanted positions as little as possible so they are at least gap apart, keeping their order.
+
+Pushes them apart to the right and to the left, and returns the middle of the two.
+ */
+fn spread(wanted: &[f64], gap: f64) -> Vec<f64> {
+    let mut right = wanted.to_vec();
+    for i in 1..right.len() {
+        right[i] = right[i].max(right[i - 1] + gap);
+    }
+    let mut left = wanted.to_vec();
+    for i in (0..left.len().saturating_sub(1)).rev() {
+        left[i] = left[i].min(left[i + 1] - gap);
+    }
+    right.iter().zip(left).map(|(a, b)| (a + b) / 2.0).collect()
+}
+
+/**
+Returns the average of the values, None if there are none.
+ */
+fn average<I: Iterator<Item = f64>>(values: I) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), x| (sum + x, count + 1));
+    if count == 0 {
+        None
+    } else {
+        Some(sum / f64::from(count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn star() -> Vec<Node> {
+        // A is linked to every other Node, The rest are not linked to each other.
+        let mut nodes = Node::from_list(&[(0, 0); 6]);
+        let copy = nodes.clone();
+        for other in copy.iter().skip(1) {
+            nodes[0].link(other);
+        }
+        nodes
+    }
+
+    fn distance(a: &Node, b: &Node) -> u32 {
+        coordinate::distance(a.geo(), b.geo())
+    }
+
+    #[test]
+    fn empty() {
+        let nodes: Vec<Node> = vec![];
+        assert!(force_directed(nodes, &ForceDirected::default()).is_empty());
+    }
+
+    #[test]
+    fn stays_inside_bounds() {
+        let (min, max) = (Coordinate::new(-50, 20), Coordinate::new(50, 80));
+        let settings = ForceDirected::new(min, max).repulsion(10.0);
+        for node in force_directed(star(), &settings) {
+            assert!(node.x() >= min.x && node.x() <= max.x);
+            assert!(node.y() >= min.y && node.y() <= max.y);
+        }
+    }
+
+    #[test]
+    fn same_seed_same_layout() {
+        let positions = |seed| {
+            let settings = ForceDirected::default().seed(seed);
+            force_directed(star(), &settings)
+                .iter()
+                .map(|node| node.geo())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(positions(42), positions(42));
+        assert_ne!(positions(42), positions(43));
+    }
+
+    #[test]
+    fn seed_is_portable() {
+        let settings = ForceDirected::default().iterations(0).seed(7);
+        let nodes = force_directed(star(), &settings);
+        let positions = nodes.iter().map(|node| node.geo()).collect::<Vec<_>>();
+        // Pinned, so changing how the starting positions are drawn is noticed.
+        let expected = Coordinate::from_list(&[
+            (79, 84),
+            (352, 363),
+            (301, 180),
+            (42, 425),
+            (182, 495),
+            (100, 192),
+        ]);
+        assert_eq!(positions, expected);
+    }
+
+    #[test]
+    fn linked_are_closer() {
+        let nodes = force_directed(star(), &ForceDirected::default());
+        let linked = (1..6)
+            .map(|i| distance(&nodes[0], &nodes[i]))
+            .max()
+            .unwrap();
+        let unlinked = (2..6)
+            .map(|i| distance(&nodes[1], &nodes[i]))
+            .min()
+            .unwrap();
+        assert!(linked < unlinked, "{} {}", linked, unlinked);
+    }
+
+    #[test]
+    fn repulsion_spreads() {
+        let spread = |repulsion| {
+            let settings = ForceDirected::default().repulsion(repulsion);
+            let nodes = force_directed(star(), &settings);
+            (1..6).map(|i| distance(&nodes[0], &nodes[i])).sum::<u32>()
+        };
+        assert!(spread(0.2) < spread(4.0));
+    }
+
+    #[test]
+    fn links_follow() {
+        let nodes = force_directed(star(), &ForceDirected::default());
+        for (i, link) in nodes[0].links.iter().enumerate() {
+            assert_eq!(link.from, Some(nodes[0].world()));
+            assert_eq!(link.to, Some(nodes[i + 1].world()));
+        }
+    }
+
+    #[test]
+    fn groups_move_whole() {
+        let mut groups = Group::from_list(&[(0, 0), (0, 0), (0, 0)]);
+        for (i, group) in groups.iter_mut().enumerate() {
+            group.push(Node::new(&i.to_string(), Coordinate::new(10, 10)));
+        }
+        let copy = groups.clone();
+        groups[0].nodes[0].link(&copy[1].nodes[0]);
+
+        let groups = force_directed(groups, &ForceDirected::default());
+        assert_eq!(groups[2].nodes[0].geo(), Coordinate::new(10, 10));
+
+        // The link is drawn relative to the first Group.
+        let link = &groups[0].nodes[0].links[0];
+        let to = groups[1].world() + groups[1].nodes[0].world() - groups[0].world();
+        assert_eq!(link.to, Some(to));
+    }
+
+    fn linked(count: usize, links: &[(usize, usize)]) -> Vec<Node> {
+        let mut nodes = Node::from_list(&vec![(0, 0); count]);
+        let copy = nodes.clone();
+        for &(from, to) in links {
+            nodes[from].link(&copy[to]);
+        }
+        nodes
+    }
+
+    #[test]
+    fn ranks_by_longest_path() {
+        let nodes = linked(4, &[(0, 1), (1, 2), (0, 2), (3, 0)]);
+        let nodes = hierarchical(nodes, &Hierarchical::default().layer_gap(10));
+        let ranks = nodes.iter().map(|node| node.y()).collect::<Vec<_>>();
+        assert_eq!(ranks, vec![10, 20, 30, 0]);
+    }
+
+    #[test]
+    fn cycles_are_layered() {
+        let nodes = linked(3, &[(0, 1), (1, 2), (2, 0)]);
+        assert_eq!(acyclic(3, &links(&nodes)), vec![(0, 1), (0, 2), (1, 2)]);
+
+        let nodes = hierarchical(nodes, &Hierarchical::default());
+        let ranks = nodes.iter().map(|node| node.y()).collect::<Vec<_>>();
+        assert_eq!(ranks, vec![0, 60, 120]);
+    }
+
+    #[test]
+    fn crossings_are_removed() {
+        // A -> D and B -> C cross when the layers are kept in order.
+        let nodes = linked(4, &[(0, 3), (1, 2)]);
+        let layers = vec![vec![0, 1], vec![2, 3]];
+        let (up, down) = (
+            vec![vec![], vec![], vec![1], vec![0]],
+            vec![vec![3], vec![2], vec![], vec![]],
+        );
+        assert_eq!(crossings(&layers, &down), 1);
+        assert_eq!(crossings(&order(layers, &up, &down, 1), &down), 0);
+
+        let nodes = hierarchical(nodes, &Hierarchical::default());
+        assert_eq!(nodes[0].x() < nodes[1].x(), nodes[3].x() < nodes[2].x());
+    }
+
+    #[test]
+    fn layers_are_spread() {
+        let links = (1..6).map(|i| (0, i)).chain((1..6).map(|i| (i, 6)));
+        let nodes = linked(7, &links.collect::<Vec<_>>());
+        let settings = Hierarchical::new(Coordinate::new(-20, 5)).node_gap(25);
+        let nodes = hierarchical(nodes, &settings);
+
+        let mut columns = nodes[1..6].iter().map(|node| node.x()).collect::<Vec<_>>();
+        columns.sort_unstable();
+        assert!(columns.windows(2).all(|pair| pair[1] - pair[0] >= 25));
+        assert_eq!(columns[0], -20);
+
+        // The first and last Nodes are centred below and above the others.
+        assert_eq!(nodes[0].x(), columns[2]);
+        assert_eq!(nodes[6].x(), columns[2]);
+        assert_eq!(nodes[0].y(), 5);
+    }
+
+    #[test]
+    fn long_links_make_room() {
+        // A -> D skips a layer, and passes beside B -> C instead of through them.
+        let nodes = linked(4, &[(0, 1), (1, 2), (0, 3), (2, 3)]);
+        let nodes = hierarchical(nodes, &Hierarchical::default());
+        assert_eq!(nodes[3].y(), 180);
+        assert_ne!(nodes[1].x(), nodes[0].x());
+
+        // The long link bends at the virtual elements, so it is drawn around B and C.
+        let link = nodes[0]
+            .links
+            .iter()
+            .find(|link| link.t == nodes[3].hash)
+            .unwrap();
+        assert_eq!(link.via.len(), 2);
+        assert_eq!((link.via[0].y, link.via[1].y), (60.0, 120.0));
+
+        // Moving the Nodes again drops the bends.
+        let nodes = force_directed(nodes, &ForceDirected::default());
+        assert!(nodes[0].links.iter().all(|link| link.via.is_empty()));
+    }
+
+    #[test]
+    fn long_links_bend_around() {
+        // A -> C skips a layer, where a straight edge would pass through D.
+        let nodes = linked(4, &[(0, 1), (0, 2), (0, 3), (1, 2)]);
+        let nodes = hierarchical(nodes, &Hierarchical::default());
+        let link = nodes[0]
+            .links
+            .iter()
+            .find(|link| link.t == nodes[2].hash)
+            .unwrap();
+        let crosses = |points: &[Coordinate]| {
+            let size = nodes[3].size() as i32;
+            points
+                .windows(2)
+                .flat_map(|w| tools::plot(w[0], w[1]))
+                .any(|c| (c.x - nodes[3].x()).abs() <= size && (c.y - nodes[3].y()).abs() <= size)
+        };
+        assert!(crosses(&[nodes[0].geo(), nodes[2].geo()]));
+        assert!(!crosses(&tools::round(&link.world_waypoints())));
+    }
+
+    #[test]
+    fn spread_keeps_order() {
+        assert_eq!(spread(&[0.0, 0.0, 0.0], 10.0), vec![-10.0, 0.0, 10.0]);
+        assert_eq!(spread(&[0.0, 50.0], 10.0), vec![0.0, 50.0]);
+        assert!(spread(&[], 10.0).is_empty());
+    }
+}
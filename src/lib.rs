@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::derive_hash_xor_eq))]
+#![allow(clippy::derived_hash_with_manual_eq)]
 
 extern crate gif;
 extern crate image;
@@ -14,12 +14,15 @@ pub mod macros;
 pub mod consts;
 pub mod coordinate;
 pub mod data;
+pub mod error;
 pub mod group;
+pub mod layout;
 pub mod map;
 pub mod node;
 pub mod tools;
 pub mod traits;
 
+pub use error::Error;
 pub use traits::*;
 
 #[cfg(test)]
@@ -31,11 +34,25 @@ mod tests;
 Holds a Coordinate on a x and y plane.
 It's implemented in Nodes, Groups, HL and the Location trait to enable
 differennt structures to be drawn.
+
+The operators saturate at the bounds of i32, Coordinate::checked_add and its
+siblings return None instead.
  */
 #[derive(Debug, Eq, Copy, Clone, Default)]
 pub struct Coordinate {
-    pub x: i16,
-    pub y: i16,
+    pub x: i32,
+    pub y: i32,
+}
+
+/**
+A position in world space, kept with sub-pixel precision.
+
+Converted to a Coordinate by rounding to the nearest whole position.
+ */
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
 }
 
 /*
@@ -50,35 +67,78 @@ A links to B.
 
 This means that B does not know that A is connected to it.
 
+Unless the link is undirected, in which case it can also be travelled from B to A.
+Only A stores the link, so it is drawn once.
+
 
 Deleting node B in this scenario would impact drawing and pathing.
 But does not have any direct impact on the HL since it only stores a Hash reference to the node it is linked to.
+
+
+The weight is the cost of travelling the link when pathing.
+If it is not set the distance between the two endpoints is used.
+
+The names of the endpoints are kept if they are known, and used when displayed.
+The size of the Node at the to end can be kept as well, so the arrowhead stops at its edge.
+
+When drawn, an optional arrowhead points at the to end, and the label is written halfway along the edge.
+The edge is drawn width positions wide in its own color, which may be transparent.
+It bends at the via positions on the way, which are set by layouts such as layout::hierarchical.
  */
-#[derive(Copy, PartialEq, Eq, Clone, Debug, Default)]
+#[derive(PartialEq, Clone, Debug)]
 pub struct HL {
     pub style: EdgeStyle,
     pub f: u64,
     pub t: u64,
-    pub from: Option<Coordinate>,
-    pub to: Option<Coordinate>,
+    pub from: Option<Point>,
+    pub to: Option<Point>,
+    pub via: Vec<Point>,
+    pub to_size: Option<u32>,
+    pub from_name: Option<String>,
+    pub to_name: Option<String>,
+    pub weight: Option<u32>,
+    pub label: Option<String>,
+    pub undirected: bool,
+    pub arrow: Option<u32>,
+    pub color: image::Rgba<u8>,
+    pub width: u32,
 }
 
 /**
 A Location object that can be drawn on an image, along with set size and color.
+
+The name it was created with is kept, along with any key/value metadata.
+An optional text label is drawn next to it when mapped.
+An optional Shape is drawn instead of the one given to the Map.
+
+The position is kept in world space with sub-pixel precision, geo is the nearest whole position.
+
+The shape is filled with color, and outlined stroke_width positions wide when a stroke color is set.
+Hollow nodes are not filled, and outlined in their color unless a stroke color is set.
  */
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct Node {
     pub hash: u64,
-    pub geo: Coordinate,
     pub color: image::Rgba<u8>,
     pub radius: Option<u32>,
-    links: [HL; consts::MAX_LINKS],
+    pub shape: Option<Shape>,
+    pub stroke: Option<image::Rgba<u8>>,
+    pub stroke_width: u32,
+    pub hollow: bool,
+    links: Vec<HL>,
+    name: Option<String>,
+    meta: std::collections::BTreeMap<String, String>,
+    label: Option<String>,
+    placement: Placement,
+    world: Point,
+    geographic: bool,
 }
 
 /**
 Holds a set of nodes and applies properties to all child nodes when drawn.
 
 The group itself has no displayed output and is not visible.
+Nodes without a Shape of their own are drawn with the Shape of the Group, if it has one.
 
 It contains a Node used for Group meta data.
  */
@@ -90,11 +150,28 @@ pub struct Group {
 
 /**
 High abstraction Map which helps position objects.
+
+Draws on to any Canvas, an image by default or a svg document if created using Map::svg.
+
+The canvas is sized to fit the first elements mapped, unless a size is set.
+Elements mapped later are drawn with the same placement, so a viewport is needed to fit later layers.
  */
-#[derive(Clone, Debug, Default)]
-pub struct Map {
-    image: Option<IW>,
+#[derive(Clone, Debug)]
+pub struct Map<C: Canvas = IW> {
+    canvas: Option<C>,
     add: Coordinate,
+    labels: bool,
+    anti_alias: bool,
+    mode: BlendMode,
+    size: Option<Coordinate>,
+    margin: Option<u32>,
+    viewport: Option<(Coordinate, Coordinate)>,
+    scale: ScaleMode,
+    transform: map::view::Transform,
+    placement: map::view::Transform,
+    projection: Option<Projection>,
+    origin: Coordinate,
+    view: map::view::View,
 }
 
 /**
@@ -102,10 +179,13 @@ Enables traversing through a network of connected nodes.
 
 
 Checking if a path is valid and setting new paths.
+
+Elements are stored by their hash, so any number of elements can be held
+as long as no two of them share the same hash.
  */
-#[derive(Clone, Copy)]
-pub struct Network<T: Draw + Hash + std::marker::Copy> {
-    pub hash_map: [Option<T>; consts::NETWORK_REM],
+#[derive(Clone, Debug)]
+pub struct Network<T: Draw + Hash> {
+    pub hash_map: std::collections::HashMap<u64, T>,
 }
 
 // ------------------------------------------------------------------
@@ -129,24 +209,168 @@ Curves the connection
 
 Uses Brasehem's line algorithm to directly correct the nodes.
 
+
+## Bezier
+
+Smooth S shaped curve that leaves and enters the nodes horizontally.
+
+
+## Orthogonal
+
+Only horizontal and vertical lines, bending twice halfway between the nodes.
+
  */
-#[derive(Copy, PartialEq, Eq, Clone, Debug)]
+#[derive(Copy, PartialEq, Eq, Clone, Debug, Default)]
 pub enum EdgeStyle {
+    #[default]
     Direct,
     Ellipse,
     Straight,
+    Bezier,
+    Orthogonal,
+}
+
+/**
+Where a label is drawn relative to the Node or Group it belongs to.
+
+
+## Above
+
+Centred horizontally above the element.
+
+
+## Below
+
+Centred horizontally below the element.
+
+
+## Center
+
+Centred on the position of the element.
+
+ */
+#[derive(Copy, PartialEq, Eq, Clone, Debug, Default)]
+pub enum Placement {
+    Above,
+    #[default]
+    Below,
+    Center,
+}
+
+/**
+How a Map of a fixed size scales the drawn area to the space inside its margin.
+
+
+## Fit
+
+Scales evenly until the whole area fits, centred with empty space along the shorter side.
+
+
+## Fill
+
+Scales evenly until the whole space is covered, centred with the overflowing sides cut off.
+
+
+## Stretch
+
+Scales the width and height separately so the area covers the space exactly.
+
+ */
+#[derive(Copy, PartialEq, Eq, Clone, Debug, Default)]
+pub enum ScaleMode {
+    #[default]
+    Fit,
+    Fill,
+    Stretch,
+}
+
+/**
+How the Map draws world positions created from latitude and longitude, See Node::from_lat_lon.
+
+
+## Equirectangular
+
+Latitude and longitude are evenly spaced, as they are stored.
+
+
+## WebMercator
+
+Used by most web maps, Stretches north-south distances away from the equator so shapes keep their angles.
+Latitudes further than consts::MERCATOR_MAX_LAT from the equator are drawn at that latitude.
+
+ */
+#[derive(Copy, PartialEq, Eq, Clone, Debug, Default)]
+pub enum Projection {
+    #[default]
+    Equirectangular,
+    WebMercator,
+}
+
+/**
+How colors are combined with the pixels already drawn on an image.
+
+
+## Replace
+
+Overwrites the existing pixel, The last drawn color wins.
+
+
+## Over
+
+Source-over compositing, Transparent colors let the existing pixel show through.
+
+
+## Add
+
+Adds the colors together, so overlapping elements grow brighter.
+
+
+## Multiply
+
+Multiplies the colors together, so overlapping elements grow darker.
+
+ */
+#[derive(Copy, PartialEq, Eq, Clone, Debug, Default)]
+pub enum BlendMode {
+    Replace,
+    #[default]
+    Over,
+    Add,
+    Multiply,
 }
 
 /**
  Creates a shape of coordinate points.
 
  Sampled from a Area.
+
+
+ ## Circle and Ring
+
+ Centred on the position of the element, Ring only draws the outline
+ consts::DEFAULT_RING_WIDTH positions wide.
+
+
+ ## Square, Triangle, Hexagon, Diamond and Star
+
+ Fill the box of the element's size with the top-left corner at its position.
+
+
+ ## Custom
+
+ Exactly the given positions relative to the top-left corner, regardless of size.
+ Can be read from a small image with Shape::from_image.
 */
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Shape {
     Circle,
     Square,
     Triangle,
+    Hexagon,
+    Diamond,
+    Ring,
+    Star,
+    Custom(Vec<Coordinate>),
 }
 
 // ------------------------------------------------------------------
@@ -162,19 +386,133 @@ impl Shape {
      Algorithm is derived from:
      https://en.wikipedia.org/wiki/Midpoint_circle_algorithm
 
+
+     ## Examples
+
+     ```
+     # use pathtracer::*;
+     let square = Shape::Square.area(4);
+     assert_eq!(square.len(), 16);
+
+     let custom = Shape::Custom(Coordinate::from_list(&[(0, 0), (2, 2)]));
+     assert_eq!(custom.area(100).len(), 2);
+     ```
     */
     pub fn area(&self, area: usize) -> Vec<Coordinate> {
-        match *self {
+        match self {
             Shape::Circle => Shape::circle_area(area),
             Shape::Square => Shape::square_area(area),
-            Shape::Triangle => Shape::triangle_area(area),
+            Shape::Ring => Shape::ring_area(area),
+            Shape::Custom(points) => points.clone(),
+            _ => tools::polygon_area(&self.corners(area).unwrap_or_default()),
+        }
+    }
+
+    /**
+     Returns the radius Circle and Ring shapes of the given size are drawn with, around the position they are placed at.
+
+     The centre position is counted in the size, so the radius is one less. Every Canvas draws circles with this radius.
+
+
+     ## Examples
+
+     ```
+     # use pathtracer::*;
+     assert_eq!(Shape::radius(5), 4);
+     assert_eq!(Shape::radius(0), 0);
+     ```
+    */
+    pub fn radius(size: usize) -> usize {
+        size.saturating_sub(1)
+    }
+
+    /**
+     Returns the corners of the shape if it is a polygon, from the top going clockwise.
+
+     The corners fit inside a box of the given size with its top-left corner at 0 0.
+
+
+     ## Examples
+
+     ```
+     # use pathtracer::*;
+     let diamond = Shape::Diamond.corners(9).unwrap();
+     assert_eq!(diamond, Coordinate::from_list(&[(4, 0), (8, 4), (4, 8), (0, 4)]));
+     assert_eq!(Shape::Circle.corners(9), None);
+     ```
+    */
+    pub fn corners(&self, size: usize) -> Option<Vec<Coordinate>> {
+        let s = size.saturating_sub(1) as f64;
+        let at = |x: f64, y: f64| Coordinate::new(x.round() as i32, y.round() as i32);
+        match self {
+            Shape::Triangle => Some(vec![
+                coordinate!(size / 2, 0),
+                coordinate!(s, size),
+                coordinate!(0, size),
+            ]),
+            Shape::Hexagon => Some(vec![
+                at(s / 4.0, 0.0),
+                at(s * 3.0 / 4.0, 0.0),
+                at(s, s / 2.0),
+                at(s * 3.0 / 4.0, s),
+                at(s / 4.0, s),
+                at(0.0, s / 2.0),
+            ]),
+            Shape::Diamond => Some(vec![
+                at(s / 2.0, 0.0),
+                at(s, s / 2.0),
+                at(s / 2.0, s),
+                at(0.0, s / 2.0),
+            ]),
+            Shape::Star => {
+                let r = s / 2.0;
+                Some(
+                    (0..10)
+                        .map(|i| {
+                            let reach = if i % 2 == 0 { r } else { r * 0.4 };
+                            let angle = f64::from(i) * std::f64::consts::PI / 5.0
+                                - std::f64::consts::FRAC_PI_2;
+                            at(r + reach * angle.cos(), r + reach * angle.sin())
+                        })
+                        .collect(),
+                )
+            }
+            _ => None,
         }
     }
 
+    /**
+     Creates a Custom shape from the positions in the image that are not fully transparent.
+
+     The colors of the image are ignored, the shape is drawn in the color of the element.
+
+
+     ## Examples
+
+     ```
+     # use pathtracer::*;
+     let mut image = image::RgbaImage::new(3, 3);
+     image.put_pixel(1, 2, image::Rgba([255, 0, 0, 255]));
+     assert_eq!(
+         Shape::from_image(&image),
+         Shape::Custom(vec![Coordinate::new(1, 2)])
+     );
+     ```
+    */
+    pub fn from_image(image: &image::RgbaImage) -> Self {
+        Shape::Custom(
+            image
+                .enumerate_pixels()
+                .filter(|(_, _, p)| p.data[3] > 0)
+                .map(|(x, y, _)| coordinate!(x, y))
+                .collect(),
+        )
+    }
+
     fn circle_area(area: usize) -> Vec<Coordinate> {
         let mut vec = Vec::new();
-        let mut pos = coordinate!((area - 1), 0);
-        let mut err: i16 = 1 - (area << 1) as i16;
+        let mut pos = coordinate!(Shape::radius(area), 0);
+        let mut err: i32 = 1 - (area << 1) as i32;
         let mut d = Coordinate::new(err, 1);
 
         let q_plot = |x1, y1, x2, y2| tools::plot(coordinate!(x1, y1), coordinate!(x2, y2));
@@ -208,22 +546,142 @@ impl Shape {
         })
     }
 
-    fn triangle_area(area: usize) -> Vec<Coordinate> {
-        (0..area).fold(vec![], |mut acc, x| {
-            acc.append(&mut tools::plot(
-                coordinate!(area / 2, 0),
-                coordinate!(x, area),
-            ));
-            acc
-        })
+    /**
+     Returns the coordinates the shape occupies paired with their coverage from 0.0 to 1.0, for anti-aliased drawing.
+
+     Curved and slanted edges are partially covered, Square and Custom shapes are fully covered.
+    */
+    pub fn coverage(&self, size: usize) -> Vec<(Coordinate, f64)> {
+        let radius = Shape::radius(size) as f64;
+        match self {
+            Shape::Circle => tools::circle_coverage(radius),
+            Shape::Ring => tools::ring_coverage(radius, f64::from(consts::DEFAULT_RING_WIDTH)),
+            _ => match self.corners(size) {
+                Some(corners) => tools::polygon_coverage(&corners),
+                None => self.area(size).into_iter().map(|c| (c, 1.0)).collect(),
+            },
+        }
+    }
+
+    /**
+     Returns the coordinates of the shape that are at most width positions from its edge.
+
+     Works for every shape, since it only looks at which positions the area contains.
+
+
+     ## Examples
+
+     ```
+     # use pathtracer::*;
+     let outline = Shape::Square.outline(4, 1);
+     assert_eq!(outline.len(), 12);
+     assert!(!outline.contains(&Coordinate::new(1, 1)));
+     assert_eq!(Shape::Square.outline(4, 2).len(), 16);
+     ```
+    */
+    pub fn outline(&self, size: usize, width: u32) -> Vec<Coordinate> {
+        let area = tools::unique(self.area(size));
+        let set: std::collections::HashSet<(i32, i32)> = area.iter().map(|c| (c.x, c.y)).collect();
+        let w = width as i32;
+        area.into_iter()
+            .filter(|c| {
+                width > 0 && (-w..=w).any(|y| (-w..=w).any(|x| !set.contains(&(c.x + x, c.y + y))))
+            })
+            .collect()
+    }
+
+    fn ring_area(area: usize) -> Vec<Coordinate> {
+        let width = usize::from(consts::DEFAULT_RING_WIDTH);
+        if area <= width {
+            return Shape::circle_area(area);
+        }
+        let inner: std::collections::HashSet<(i32, i32)> = Shape::circle_area(area - width)
+            .into_iter()
+            .map(|c| (c.x, c.y))
+            .collect();
+        Shape::circle_area(area)
+            .into_iter()
+            .filter(|c| !inner.contains(&(c.x, c.y)))
+            .collect()
     }
 }
 
-// ------------------------------------------------------------------
+impl BlendMode {
+    /**
+    Combines the source color with the destination color, with the alpha of the source scaled by coverage.
 
-impl std::default::Default for EdgeStyle {
-    fn default() -> Self {
-        EdgeStyle::Direct
+    Coverage ranges from 0.0 to 1.0, and is the share of the pixel the source covers.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let red = image::Rgba([255, 0, 0, 128]);
+    let blue = image::Rgba([0, 0, 255, 255]);
+    assert_eq!(BlendMode::Replace.mix(red, blue, 1.0), red);
+    assert_eq!(BlendMode::Over.mix(red, blue, 1.0), image::Rgba([128, 0, 127, 255]));
+    assert_eq!(BlendMode::Add.mix(red, blue, 1.0), image::Rgba([128, 0, 255, 255]));
+    assert_eq!(BlendMode::Multiply.mix(red, blue, 1.0), image::Rgba([0, 0, 127, 255]));
+    ```
+     */
+    pub fn mix(self, src: image::Rgba<u8>, dst: image::Rgba<u8>, coverage: f64) -> image::Rgba<u8> {
+        let coverage = coverage.clamp(0.0, 1.0);
+        let channel = |c: &image::Rgba<u8>, i: usize| f64::from(c[i]) / 255.0;
+        let to_u8 = |x: f64| (x.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        if self == BlendMode::Replace {
+            let lerp =
+                |i| to_u8(channel(&dst, i) + (channel(&src, i) - channel(&dst, i)) * coverage);
+            return image::Rgba([lerp(0), lerp(1), lerp(2), lerp(3)]);
+        }
+
+        let sa = channel(&src, 3) * coverage;
+        let da = channel(&dst, 3);
+        let alpha = match self {
+            BlendMode::Add => (sa + da).min(1.0),
+            _ => sa + da * (1.0 - sa),
+        };
+        if alpha <= 0.0 {
+            return image::Rgba([0; 4]);
+        }
+
+        // Premultiplied color of the result, See the W3C compositing and blending specification.
+        let mix = |i| {
+            let (s, d) = (channel(&src, i), channel(&dst, i));
+            let premultiplied = match self {
+                BlendMode::Add => s * sa + d * da,
+                BlendMode::Multiply => s * sa * (1.0 - da) + d * da * (1.0 - sa) + s * d * sa * da,
+                _ => s * sa + d * da * (1.0 - sa),
+            };
+            to_u8(premultiplied / alpha)
+        };
+        image::Rgba([mix(0), mix(1), mix(2), to_u8(alpha)])
+    }
+}
+
+impl Placement {
+    /**
+    Returns the top-left and bottom-right corner of the text, placed next to an element.
+
+    The element is centred on center and spans from top to bottom.
+     */
+    fn area(
+        self,
+        text: &str,
+        center: Coordinate,
+        top: i32,
+        bottom: i32,
+    ) -> (Coordinate, Coordinate) {
+        let size = map::font::text_size(text);
+        let gap = consts::DEFAULT_LABEL_GAP as i32;
+        let y = match self {
+            Placement::Above => top - gap - size.y,
+            Placement::Below => bottom + gap,
+            Placement::Center => center.y - size.y / 2,
+        };
+        let min = coordinate!(center.x - size.x / 2, y);
+        (min, min + size)
     }
 }
 
@@ -236,18 +694,30 @@ impl Hash for HL {
     fn hash(&self) -> u64 {
         self.t
     }
+
+    fn name(&self) -> Option<&str> {
+        self.to_name.as_deref()
+    }
 }
 
 impl Hash for Node {
     fn hash(&self) -> u64 {
         self.hash
     }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
 }
 
 impl Hash for Group {
     fn hash(&self) -> u64 {
         self.settings.hash()
     }
+
+    fn name(&self) -> Option<&str> {
+        self.settings.name()
+    }
 }
 
 // ------------------------------------------------------------------
@@ -259,8 +729,38 @@ impl std::fmt::Display for Coordinate {
 }
 
 impl std::fmt::Display for HL {
+    /**
+    Displays the names of the endpoints, or their hashes if the names are unknown.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    # fn main() -> Result<(), Error> {
+    let b = Node::new("B", Coordinate::new(10, 10));
+    let mut a = Node::new("A", Coordinate::new(0, 0));
+    a.link(&b);
+    a.hl_mut(0)?.label("road");
+    assert_eq!(a.hl(0)?.to_string(), "A -> B (road)");
+    assert_eq!(HL::new(1, 2).to_string(), "1 -> 2");
+    # Ok(())
+    # }
+    ```
+     */
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{} -> {}", self.f, self.t)
+        match &self.from_name {
+            Some(name) => write!(f, "{}", name)?,
+            None => write!(f, "{}", self.f)?,
+        }
+        match &self.to_name {
+            Some(name) => write!(f, " -> {}", name)?,
+            None => write!(f, " -> {}", self.t)?,
+        }
+        match &self.label {
+            Some(label) => write!(f, " ({})", label),
+            None => Ok(()),
+        }
     }
 }
 
@@ -272,6 +772,8 @@ Image wrapper around the Image crate to enable better debugging panics.
 #[derive(Clone, Debug)]
 pub struct IW {
     img: image::ImageBuffer<image::Rgba<u8>, Vec<u8>>,
+    anti_alias: bool,
+    mode: BlendMode,
 }
 
 impl IW {
@@ -285,10 +787,65 @@ impl IW {
     /**
     Wraps around Image put_pixel but indicates failed positions.
 
+    The color is combined with the existing pixel using the blend mode, See IW::set_blend_mode.
+
     Set debug_assertions flag to panic for out of bounds positions with improved debugging messages.
      */
     pub fn put<L: Location>(&mut self, l: &L, color: image::Rgba<u8>) {
-        self.img.put_pixel(l.x() as u32, l.y() as u32, color);
+        let (x, y) = (l.x() as u32, l.y() as u32);
+        let color = match self.mode {
+            BlendMode::Replace => color,
+            mode => mode.mix(color, *self.img.get_pixel(x, y), 1.0),
+        };
+        self.img.put_pixel(x, y, color);
+    }
+
+    /**
+    Returns how colors are combined with the existing pixels.
+     */
+    pub fn blend_mode(&self) -> BlendMode {
+        self.mode
+    }
+
+    /**
+    Sets how colors are combined with the existing pixels, Source-over compositing is used by default.
+     */
+    pub fn set_blend_mode(&mut self, mode: BlendMode) {
+        self.mode = mode;
+    }
+
+    /**
+    Combines the color with the existing pixel using the blend mode, with the alpha of the color scaled by coverage.
+
+    Positions outside the image are skipped, since anti-aliased edges can reach one position past a shape.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let mut iw = IW::blank(1, 1);
+    iw.put(&Coordinate::new(0, 0), image::Rgba([0, 0, 255, 255]));
+    iw.blend(&Coordinate::new(0, 0), image::Rgba([255, 0, 0, 255]), 0.5);
+    assert_eq!(iw.image().get_pixel(0, 0), &image::Rgba([128, 0, 128, 255]));
+    ```
+     */
+    pub fn blend<L: Location>(&mut self, l: &L, color: image::Rgba<u8>, coverage: f64) {
+        if !self.contains(l) {
+            return;
+        }
+
+        let (x, y) = (l.x() as u32, l.y() as u32);
+        let color = self.mode.mix(color, *self.img.get_pixel(x, y), coverage);
+        self.img.put_pixel(x, y, color);
+    }
+
+    /**
+    Returns true if the position is inside the image.
+     */
+    pub fn contains<L: Location>(&self, l: &L) -> bool {
+        let (x, y) = (l.x(), l.y());
+        x >= 0 && y >= 0 && (x as u32) < self.img.width() && (y as u32) < self.img.height()
     }
 
     /**
@@ -299,8 +856,49 @@ impl IW {
     }
 }
 
+impl Canvas for IW {
+    fn blank(width: u32, height: u32) -> Self {
+        IW {
+            img: map::gen_canvas(width, height),
+            anti_alias: false,
+            mode: BlendMode::default(),
+        }
+    }
+
+    /**
+    Positions outside the image are skipped, so elements outside a viewport are cut off.
+     */
+    fn put(&mut self, pos: Coordinate, color: image::Rgba<u8>) {
+        if self.contains(&pos) {
+            IW::put(self, &pos, color);
+        }
+    }
+
+    fn blend(&mut self, pos: Coordinate, color: image::Rgba<u8>, coverage: f64) {
+        IW::blend(self, &pos, color, coverage);
+    }
+
+    fn anti_aliased(&self) -> bool {
+        self.anti_alias
+    }
+
+    fn set_anti_aliased(&mut self, enabled: bool) {
+        self.anti_alias = enabled;
+    }
+
+    fn set_blend_mode(&mut self, mode: BlendMode) {
+        IW::set_blend_mode(self, mode);
+    }
+}
+
 // ------------------------------------------------------------------
 
+impl Default for HL {
+    fn default() -> Self {
+        HL::new(0, 0)
+    }
+}
+
 impl Find for HL {}
 
 impl Find for Node {}
@@ -311,7 +909,7 @@ impl Find for Group {
      */
     fn find<H: Hash>(&self, hash: H) -> Option<Coordinate> {
         let f = tools::find(hash.hash(), &self.nodes);
-        f.and_then(|x| Some(x.position()))
+        f.map(|x| x.position())
     }
 }
 
@@ -319,19 +917,22 @@ impl Find for Group {
 
 impl MinMax for HL {
     fn min_max(&self) -> (Coordinate, Coordinate) {
-        let zero = coordinate!();
-        let to = self.to.unwrap_or(zero);
-        (self.position(), to)
+        let to = self.to.unwrap_or_default();
+        (self.position(), Coordinate::from(to))
     }
 }
 
 impl MinMax for Node {
     fn min_max(&self) -> (Coordinate, Coordinate) {
-        let mut max = coordinate!(consts::DEFAULT_SIZE);
+        let mut max = coordinate!(self.size());
         let mut min = self.position();
         min -= max;
-        max += self.geo;
-        (min, max)
+        max += self.geo();
+        self.links
+            .iter()
+            .flat_map(|link| link.label_area().into_iter().chain(link.arrow_area()))
+            .chain(self.label_area())
+            .fold((min, max), coordinate::union)
     }
 }
 
@@ -354,16 +955,9 @@ impl MinMax for Group {
     ```
     */
     fn min_max(&self) -> (Coordinate, Coordinate) {
-        let mut min = coordinate!(0, 0);
-        let mut max = coordinate!(0, 0);
-        for node in &self.nodes {
-            let (min2, max2) = node.min_max();
-            max.x = std::cmp::max(max.x, max2.x);
-            min.x = std::cmp::min(min.x, min2.x);
-            max.y = std::cmp::max(max.y, max2.y);
-            min.y = std::cmp::min(min.y, min2.y);
-        }
-        (min + self.position(), max + self.position())
+        let area = self.nodes_min_max();
+        self.label_area()
+            .map_or(area, |label| coordinate::union(area, label))
     }
 }
 
@@ -371,21 +965,40 @@ impl MinMax for Group {
 
 impl Location for HL {
     fn position(&self) -> Coordinate {
-        let zero = coordinate!();
-        self.from.unwrap_or(zero)
+        Coordinate::from(self.world())
+    }
+
+    fn world(&self) -> Point {
+        self.from.unwrap_or_default()
     }
 }
 
 impl Location for Node {
     fn position(&self) -> Coordinate {
-        self.geo.position()
+        self.geo()
     }
-}
 
-impl Location for Group {
-    fn position(&self) -> Coordinate {
+    fn world(&self) -> Point {
+        self.world
+    }
+
+    fn geographic(&self) -> bool {
+        self.geographic
+    }
+}
+
+impl Location for Group {
+    fn position(&self) -> Coordinate {
         self.settings.position()
     }
+
+    fn world(&self) -> Point {
+        self.settings.world()
+    }
+
+    fn geographic(&self) -> bool {
+        self.settings.geographic || self.nodes.iter().any(|node| node.geographic)
+    }
 }
 
 impl Location for Coordinate {
@@ -396,36 +1009,119 @@ impl Location for Coordinate {
 
 // ------------------------------------------------------------------
 
+impl Layout for Node {
+    fn targets(&self) -> Vec<u64> {
+        self.links.iter().map(|link| link.t).collect()
+    }
+
+    fn ends(&self) -> Vec<(u64, Point)> {
+        vec![(self.hash, self.world)]
+    }
+
+    fn place(&mut self, point: Point) {
+        self.set_world(point);
+    }
+
+    fn relink(&mut self, ends: &std::collections::HashMap<u64, Point>) {
+        layout::relink(&mut self.links, self.world, ends, Point::default());
+    }
+
+    fn route(&mut self, to: &[u64], via: &[Point]) {
+        layout::route(&mut self.links, to, via, Point::default());
+    }
+}
+
+impl Layout for Group {
+    fn targets(&self) -> Vec<u64> {
+        self.nodes
+            .iter()
+            .flat_map(Layout::targets)
+            .chain(self.settings.targets())
+            .collect()
+    }
+
+    /**
+    Returns the Group itself and the Nodes inside of it, Nodes are not positioned relative to the Group.
+     */
+    fn ends(&self) -> Vec<(u64, Point)> {
+        let world = self.world();
+        self.nodes
+            .iter()
+            .map(|node| (node.hash, node.world + world))
+            .chain(self.settings.ends())
+            .collect()
+    }
+
+    fn place(&mut self, point: Point) {
+        self.settings.place(point);
+    }
+
+    /**
+    Links of the Nodes inside the Group are drawn relative to it, so their ends are moved by its position.
+     */
+    fn relink(&mut self, ends: &std::collections::HashMap<u64, Point>) {
+        let offset = Point::default() - self.world();
+        for node in &mut self.nodes {
+            layout::relink(&mut node.links, node.world, ends, offset);
+        }
+        self.settings.relink(ends);
+    }
+
+    /**
+    Links of the Nodes inside the Group are drawn relative to it, so the positions are moved by its position.
+     */
+    fn route(&mut self, to: &[u64], via: &[Point]) {
+        let offset = Point::default() - self.world();
+        for node in &mut self.nodes {
+            layout::route(&mut node.links, to, via, offset);
+        }
+        self.settings.route(to, via);
+    }
+}
+
+// ------------------------------------------------------------------
+
 impl Draw for Node {
     /**
-    Draws the node on an IW.
+    Draws the node and its links on a Canvas.
 
     It is recommended to not use this directory.
     But instead use the Map struct, which uses this trait implementation.
     */
-    fn draw(&self, mut image: IW, offset: Coordinate, shape: &Shape) -> IW {
-        let s = consts::DEFAULT_LINK_SIZE / 2;
-        let pos = self.geo + offset - coordinate!(s, s);
+    fn draw<C: Canvas>(&self, mut canvas: C, offset: Point, shape: &Shape) -> C {
+        let shape = self.shape.as_ref().unwrap_or(shape);
+        let pos = self.world + offset;
 
         for link in &self.links {
-            image = link.draw(image, offset, u32::from(consts::DEFAULT_LINK_SIZE));
+            canvas = link.draw(canvas, offset);
         }
 
-        for o in shape.area(self.size() as usize) {
-            let color = if o.x == 0 || o.y == 0 {
-                let c = self
-                    .color
-                    .data
-                    .iter()
-                    .map(|x| x.saturating_add(consts::DEFAULT_SHADE as u8))
-                    .collect::<Vec<_>>();
-                image::Rgba([c[0], c[1], c[2], c[3]])
-            } else {
-                self.color
-            };
-            image.put(&(pos + o), color);
+        if !self.hollow {
+            canvas.fill(pos, shape, self.size(), self.color);
+        }
+        let stroke = self.stroke.or(Some(self.color).filter(|_| self.hollow));
+        if let Some(stroke) = stroke {
+            canvas.stroke(pos, shape, self.size(), stroke, self.stroke_width);
+        }
+        canvas
+    }
+
+    /**
+    Draws the label of the Node and the labels of its links.
+     */
+    fn draw_label<C: Canvas>(&self, mut canvas: C, offset: Point) -> C {
+        for link in &self.links {
+            canvas = link.draw_label(canvas, offset);
         }
-        image
+        if let (Some(label), Some((min, _))) = (&self.label, self.label_area()) {
+            let part = self.world - Point::from(self.geo());
+            canvas.text(
+                Point::from(min) + part + offset,
+                label,
+                consts::DEFAULT_RGBA,
+            );
+        }
+        canvas
     }
 
     fn size(&self) -> u32 {
@@ -444,11 +1140,30 @@ impl Draw for Group {
 
     If none the Group is draw as blank.
      */
-    fn draw(&self, image: IW, mut offset: Coordinate, shape: &Shape) -> IW {
-        offset += self.position();
+    fn draw<C: Canvas>(&self, canvas: C, offset: Point, shape: &Shape) -> C {
+        let shape = self.settings.shape.as_ref().unwrap_or(shape);
+        let offset = self.offset(offset);
+        self.nodes
+            .iter()
+            .fold(canvas, |acc, node| node.draw(acc, offset, shape))
+    }
+
+    /**
+    Draws the label of the Group, followed by the labels of its Nodes.
+     */
+    fn draw_label<C: Canvas>(&self, mut canvas: C, offset: Point) -> C {
+        if let (Some(label), Some((min, _))) = (self.label(), self.label_area()) {
+            let part = self.world() - Point::from(self.position());
+            canvas.text(
+                Point::from(min) + part + offset,
+                label,
+                consts::DEFAULT_RGBA,
+            );
+        }
+        let offset = self.offset(offset);
         self.nodes
             .iter()
-            .fold(image, |acc, node| node.draw(acc, offset, shape))
+            .fold(canvas, |acc, node| node.draw_label(acc, offset))
     }
 
     fn size(&self) -> u32 {
@@ -463,7 +1178,7 @@ impl Draw for Group {
     }
 
     fn links(&self) -> &[HL] {
-        &self.settings.links()
+        self.settings.links()
     }
 }
 
@@ -472,7 +1187,7 @@ impl Draw for Group {
 impl From<Coordinate> for Node {
     fn from(c: Coordinate) -> Self {
         let mut node = node!(c);
-        node.hash = (c.x + c.y) as u64;
+        node.hash = (i64::from(c.x) + i64::from(c.y)) as u64;
         node
     }
 }
@@ -486,7 +1201,7 @@ impl From<Group> for Node {
 impl From<Coordinate> for Group {
     fn from(c: Coordinate) -> Self {
         let mut group = cluster!(c);
-        group.set().hash = (c.x + c.y) as u64;
+        group.set().hash = (i64::from(c.x) + i64::from(c.y)) as u64;
         group
     }
 }
@@ -499,6 +1214,21 @@ impl From<Node> for Group {
     }
 }
 
+impl From<Coordinate> for Point {
+    fn from(c: Coordinate) -> Self {
+        Point::new(f64::from(c.x), f64::from(c.y))
+    }
+}
+
+impl From<Point> for Coordinate {
+    /**
+    Rounds to the nearest whole position, Saturating at the bounds of i32.
+     */
+    fn from(p: Point) -> Self {
+        Coordinate::new(p.x.round() as i32, p.y.round() as i32)
+    }
+}
+
 impl From<Node> for Coordinate {
     fn from(node: Node) -> Self {
         node.position()
@@ -547,7 +1277,7 @@ impl Coordinate {
     # }
     ```
      */
-    pub fn new(x: i16, y: i16) -> Self {
+    pub fn new(x: i32, y: i32) -> Self {
         Coordinate { x, y }
     }
 
@@ -563,7 +1293,7 @@ impl Coordinate {
     assert!(c.lt(11));
     ```
      */
-    pub fn lt(self, lt: i16) -> bool {
+    pub fn lt(self, lt: i32) -> bool {
         self.x < lt || self.y < lt
     }
 
@@ -580,14 +1310,109 @@ impl Coordinate {
     ```
      */
     pub fn abs(self) -> Coordinate {
-        Coordinate::new(self.x.abs(), self.y.abs())
+        Coordinate::new(self.x.saturating_abs(), self.y.saturating_abs())
+    }
+
+    /**
+    Adds the coordinates, Returns None instead of overflowing.
+
+    The + operator saturates at the bounds of i32 instead.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::Coordinate;
+    let c = Coordinate::new(i32::MAX, 0);
+    assert_eq!(c.checked_add(Coordinate::new(1, 0)), None);
+    assert_eq!(c + Coordinate::new(1, 0), c);
+    assert_eq!(
+        c.checked_add(Coordinate::new(-1, 1)),
+        Some(Coordinate::new(i32::MAX - 1, 1))
+    );
+    ```
+     */
+    pub fn checked_add(self, other: Coordinate) -> Option<Coordinate> {
+        Some(Coordinate::new(
+            self.x.checked_add(other.x)?,
+            self.y.checked_add(other.y)?,
+        ))
+    }
+
+    /**
+    Subtracts the coordinates, Returns None instead of overflowing.
+     */
+    pub fn checked_sub(self, other: Coordinate) -> Option<Coordinate> {
+        Some(Coordinate::new(
+            self.x.checked_sub(other.x)?,
+            self.y.checked_sub(other.y)?,
+        ))
+    }
+
+    /**
+    Multiplies the coordinates, Returns None instead of overflowing.
+     */
+    pub fn checked_mul(self, other: Coordinate) -> Option<Coordinate> {
+        Some(Coordinate::new(
+            self.x.checked_mul(other.x)?,
+            self.y.checked_mul(other.y)?,
+        ))
     }
 
     /**
     Creates a list of coordinates from a list of tuples with x and y positions.
      */
-    pub fn from_list(list: &[(i16, i16)]) -> Vec<Coordinate> {
-        coordinate::from_list(&list, &|c, _i| c)
+    pub fn from_list(list: &[(i32, i32)]) -> Vec<Coordinate> {
+        coordinate::from_list(list, &|c, _i| c)
+    }
+}
+
+impl Projection {
+    /**
+    Returns the drawn position of a world position created from latitude and longitude.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let p = coordinate::from_lat_lon(60.0, 10.0);
+    assert_eq!(Projection::Equirectangular.project(p), p);
+
+    let projected = Projection::WebMercator.project(p);
+    assert_eq!(projected.x, p.x);
+    assert!(projected.y < p.y);
+    ```
+     */
+    pub fn project(self, p: Point) -> Point {
+        match self {
+            Projection::Equirectangular => p,
+            Projection::WebMercator => {
+                let (lat, _) = coordinate::to_lat_lon(p);
+                let max = consts::MERCATOR_MAX_LAT;
+                let lat = lat.clamp(-max, max).to_radians();
+                let y = (std::f64::consts::FRAC_PI_4 + lat / 2.0).tan().ln();
+                Point::new(p.x, -consts::EARTH_RADIUS * y)
+            }
+        }
+    }
+}
+
+impl Point {
+    /**
+    Constructs a Point struct.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let p = Point::new(1.4, -2.6);
+    assert_eq!(Coordinate::from(p), Coordinate::new(1, -3));
+    ```
+     */
+    pub fn new(x: f64, y: f64) -> Self {
+        Point { x, y }
     }
 }
 
@@ -600,16 +1425,170 @@ impl Node {
     pub fn new(name: &str, geo: Coordinate) -> Self {
         Node {
             hash: data::calculate_hash(&name),
-            geo,
             color: consts::DEFAULT_RGBA,
             radius: None,
-            links: [HL::new(0, 0); consts::MAX_LINKS],
+            shape: None,
+            stroke: None,
+            stroke_width: u32::from(consts::DEFAULT_STROKE_WIDTH),
+            hollow: false,
+            links: Vec::new(),
+            name: Some(name.to_string()).filter(|x| !x.is_empty()),
+            meta: std::collections::BTreeMap::new(),
+            label: None,
+            placement: Placement::default(),
+            world: Point::from(geo),
+            geographic: false,
+        }
+    }
+
+    /**
+    Returns the text label drawn next to the Node.
+     */
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /**
+    Returns the nearest whole position to the position in world space.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let mut node = Node::new("A", Coordinate::new(0, 0));
+    node.set_world(Point::new(10.4, 0.5));
+    assert_eq!(node.geo(), Coordinate::new(10, 1));
+    assert_eq!(node.world(), Point::new(10.4, 0.5));
+    ```
+     */
+    pub fn geo(&self) -> Coordinate {
+        Coordinate::from(self.world)
+    }
+
+    /**
+    Moves the node to the position in world space.
+     */
+    pub fn set_world(&mut self, point: Point) {
+        self.world = point;
+    }
+
+    /**
+    Constructs a Node at a latitude and longitude in degrees.
+
+    The world position is in metres, See coordinate::from_lat_lon.
+    Links from the Node are weighted by the great-circle distance in metres, so paths are the shortest on the ground.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    # fn main() -> Result<(), Error> {
+    let paris = Node::from_lat_lon("Paris", 48.8566, 2.3522);
+    let mut london = Node::from_lat_lon("London", 51.5074, -0.1278);
+    london.link(&paris);
+    assert_eq!(london.hl(0)?.weight, Some(343_557));
+
+    let image = Map::with_size(200, 200)
+        .projection(Projection::WebMercator)
+        .map(&[london, paris])
+        .consume();
+    assert_eq!(image.dimensions(), Coordinate::new(200, 200));
+    # Ok(())
+    # }
+    ```
+     */
+    pub fn from_lat_lon(name: &str, lat: f64, lon: f64) -> Self {
+        let mut node = Node::new(name, coordinate!());
+        node.set_world(coordinate::from_lat_lon(lat, lon));
+        node.geographic = true;
+        node
+    }
+
+    /**
+    Returns the latitude and longitude in degrees, if the Node was created with Node::from_lat_lon.
+
+    Nodes pushed in to a Group are positioned relative to it, so it is only correct outside of Groups.
+     */
+    pub fn lat_lon(&self) -> Option<(f64, f64)> {
+        if self.geographic {
+            Some(coordinate::to_lat_lon(self.world()))
+        } else {
+            None
         }
     }
 
+    /**
+    Sets the text label drawn next to the Node, An empty text removes the label.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let mut node = Node::new("A", Coordinate::new(0, 0));
+    node.set_label("Home", Placement::Above);
+    assert_eq!(node.label(), Some("Home"));
+    node.set_label("", Placement::Above);
+    assert_eq!(node.label(), None);
+    ```
+     */
+    pub fn set_label(&mut self, text: &str, placement: Placement) {
+        self.label = Some(text.to_string()).filter(|x| !x.is_empty());
+        self.placement = placement;
+    }
+
+    /**
+    Returns the top-left and bottom-right corner of the label, if the Node has one.
+     */
+    fn label_area(&self) -> Option<(Coordinate, Coordinate)> {
+        let size = self.size() as i32;
+        let label = self.label.as_ref()?;
+        Some(
+            self.placement
+                .area(label, self.geo(), self.geo().y - size, self.geo().y + size),
+        )
+    }
+
+    /**
+    Returns the value stored for a metadata key.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let mut node = Node::new("A", Coordinate::new(0, 0));
+    assert_eq!(node.name(), Some("A"));
+    node.set_meta("kind", "router");
+    assert_eq!(node.meta("kind"), Some("router"));
+    assert_eq!(node.meta("other"), None);
+    ```
+     */
+    pub fn meta(&self, key: &str) -> Option<&str> {
+        self.meta.get(key).map(String::as_str)
+    }
+
+    /**
+    Stores a metadata value, replacing any previous value for the key.
+     */
+    pub fn set_meta(&mut self, key: &str, value: &str) {
+        self.meta.insert(key.to_string(), value.to_string());
+    }
+
+    /**
+    Returns all metadata ordered by key.
+     */
+    pub fn metadata(&self) -> &std::collections::BTreeMap<String, String> {
+        &self.meta
+    }
+
     /**
     Retrive coordinate from a csv format.
 
+    Optional name, color and radius columns may follow, See node::parse.
+
     ## Examples
 
     Example file format:
@@ -630,7 +1609,7 @@ impl Node {
     assert_eq!(nodes.len(), 3);
     ```
      */
-    pub fn from_file(path: &str) -> Result<Vec<Self>, std::io::Error> {
+    pub fn from_file(path: &str) -> error::Result<Vec<Self>> {
         node::from_file(path)
     }
 
@@ -667,8 +1646,8 @@ impl Node {
     assert_eq!(nodes.len(), 0);
     ```
      */
-    pub fn from_list(list: &[(i16, i16)]) -> Vec<Self> {
-        coordinate::from_list(&list, &|c, i| {
+    pub fn from_list(list: &[(i32, i32)]) -> Vec<Self> {
+        coordinate::from_list(list, &|c, i| {
             Node::new(&std::char::from_u32(65 + i as u32).unwrap().to_string(), c)
         })
     }
@@ -735,21 +1714,20 @@ impl Node {
     ## Errors
 
 
-    If the index is larger than the available HL.
+    Error::LinkIndex if the index is larger than the available HL.
 
     The available HL can be retrieved through get_link_avail_index.
 
     You can only retrieve HL which are connected to other nodes.
 
      */
-    pub fn hl(&self, index: usize) -> std::io::Result<&HL> {
-        if index > self.get_link_avail_index() || !self.links[index].is_connected() {
-            Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "index too large",
-            ))
-        } else {
-            Ok(&self.links[index])
+    pub fn hl(&self, index: usize) -> error::Result<&HL> {
+        match self.links.get(index) {
+            Some(link) if link.is_connected() => Ok(link),
+            _ => Err(Error::LinkIndex {
+                index,
+                len: self.links.len(),
+            }),
         }
     }
 
@@ -779,25 +1757,16 @@ impl Node {
 
     ## Errors
 
-    If the index is larger than the available HL.
+    Error::LinkIndex if the index is larger than the available HL.
 
     You can only retrieve HL which are connected to other nodes.
 
      */
-    pub fn hl_mut(&mut self, index: usize) -> std::io::Result<&mut HL> {
-        if index > self.get_link_avail_index() || !self.links[index].is_connected() {
-            Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!(
-                    "index {} too large or not connected. Index: {} expected. Connection status: \
-                     {}",
-                    index,
-                    self.get_link_avail_index(),
-                    self.links[index].is_connected()
-                ),
-            ))
-        } else {
-            Ok(&mut self.links[index])
+    pub fn hl_mut(&mut self, index: usize) -> error::Result<&mut HL> {
+        let len = self.links.len();
+        match self.links.get_mut(index) {
+            Some(link) if link.is_connected() => Ok(link),
+            _ => Err(Error::LinkIndex { index, len }),
         }
     }
 
@@ -837,19 +1806,25 @@ impl Node {
         mut list: Vec<Node>,
         f: &dyn Fn(Coordinate, Coordinate) -> bool,
     ) -> Vec<Self> {
-        let mut prev = coordinate!();
+        let mut prev = Point::default();
         let mut prev_h = 0;
+        let mut prev_name = None;
+        let mut prev_size = None;
         for node in &mut list {
-            if prev_h != 0 && f(prev, node.geo) {
+            if prev_h != 0 && f(Coordinate::from(prev), node.geo()) {
                 let mut link = HL::new(node.hash, prev_h);
                 link.to = Some(prev);
-                link.from = Some(node.geo);
-                let i = node.get_link_avail_index();
-                node.links[i] = link;
+                link.to_size = prev_size;
+                link.from = Some(node.world);
+                link.to_name = prev_name;
+                link.from_name = node.name.clone();
+                node.links.push(link);
             }
 
             prev_h = node.hash();
-            prev = node.geo;
+            prev = node.world;
+            prev_name = node.name.clone();
+            prev_size = Some(node.size());
         }
         list
     }
@@ -857,6 +1832,8 @@ impl Node {
     /**
     Returns the next point which is available to link.
 
+    A Node has no upper limit on links, so this is the number of links it currently has.
+
     It is a good practice to call this method before attempting to call hl or hl_mut to having to error handle.
 
 
@@ -891,11 +1868,7 @@ impl Node {
     ```
      */
     pub fn get_link_avail_index(&self) -> usize {
-        self.links()
-            .iter()
-            .position(|x| !x.is_connected())
-            .or(Some(consts::MAX_LINKS - 1))
-            .unwrap()
+        self.links.len()
     }
 
     /**
@@ -932,7 +1905,7 @@ impl Node {
 
      */
     pub fn disconnect(&mut self) {
-        self.links = [HL::new(0, 0); consts::MAX_LINKS];
+        self.links.clear();
     }
 
     /**
@@ -966,14 +1939,67 @@ impl Node {
 
      */
     pub fn link<P: Hash + Location>(&mut self, other: &P) {
-        let i = self.get_link_avail_index();
-        self.links[i] = HL {
-            style: EdgeStyle::default(),
-            f: self.hash,
-            t: other.hash(),
-            from: Some(self.geo),
-            to: Some(other.position()),
-        };
+        let mut link = HL::new(self.hash, other.hash());
+        link.from = Some(self.world);
+        link.to = Some(other.world());
+        link.from_name = self.name.clone();
+        link.to_name = other.name().map(str::to_string);
+        if self.geographic {
+            link.weight(coordinate::geo_distance(self.world, other.world()));
+        }
+        self.links.push(link);
+    }
+
+    /**
+    Links Node self to another point with a custom cost used when pathing.
+
+
+    ## Examples
+
+    ```
+    # #[macro_use] use pathtracer::*;
+    # fn main() -> std::io::Result<()> {
+    let b = node!(100, 100);
+    let mut a = node!();
+    a.link_weighted(&b, 5);
+    assert_eq!(a.hl(0)?.weight, Some(5));
+    # Ok(())
+    # }
+    ```
+     */
+    pub fn link_weighted<P: Hash + Location>(&mut self, other: &P, weight: u32) {
+        self.link(other);
+        if let Some(link) = self.links.last_mut() {
+            link.weight(weight);
+        }
+    }
+
+    /**
+    Links Node self to another point so that the link can be travelled in both directions.
+
+    Only self stores the link, The other point does not need to link back.
+
+
+    ## Examples
+
+    ```
+    # #[macro_use] use pathtracer::*;
+    # fn main() -> std::io::Result<()> {
+    let nodes = Node::from_list(&[(0, 0), (10, 10)]);
+    let mut a = nodes[0].clone();
+    a.link_both(&nodes[1]);
+    let network = Network::new(vec![a, nodes[1].clone()])?;
+    assert!(network.path("A", "B").is_ok());
+    assert!(network.path("B", "A").is_ok());
+    # Ok(())
+    # }
+    ```
+     */
+    pub fn link_both<P: Hash + Location>(&mut self, other: &P) {
+        self.link(other);
+        if let Some(link) = self.links.last_mut() {
+            link.undirected = true;
+        }
     }
 }
 
@@ -985,6 +2011,16 @@ impl HL {
             t,
             from: None,
             to: None,
+            via: Vec::new(),
+            to_size: None,
+            from_name: None,
+            to_name: None,
+            weight: None,
+            label: None,
+            undirected: false,
+            arrow: None,
+            color: consts::DEFAULT_LINK_RGBA,
+            width: u32::from(consts::DEFAULT_LINK_SIZE),
         }
     }
 
@@ -1015,7 +2051,7 @@ impl HL {
     }
 
     /**
-    Checks if the HL has two endpoint hashes.
+    Sets the cost of travelling the edge when pathing.
 
 
     ## Examples
@@ -1023,66 +2059,315 @@ impl HL {
     ```
     # #[macro_use] use pathtracer::*;
     # fn main() -> std::io::Result<()> {
-    let b = cluster!();
+    let b = node!(30, 40);
     let mut a = node!();
     a.link(&b);
-    assert!(a.hl(0)?.is_connected());
+    assert_eq!(a.hl(0)?.cost(a.geo(), b.geo()), 50);
+    a.hl_mut(0)?.weight(7);
+    assert_eq!(a.hl(0)?.cost(a.geo(), b.geo()), 7);
     # Ok(())
     # }
     ```
      */
-    pub fn is_connected(&self) -> bool {
-        self.f != 0 && self.t != 0
+    pub fn weight(&mut self, weight: u32) {
+        self.weight = Some(weight);
     }
 
     /**
-    Draws the HL on an Image Wrapper.
+    Sets the size of the Node at the to end, so the arrowhead stops at its edge instead of at the default size.
 
-    Will not draw the Edge if it is not connected, or if the the HL's from and to connections are the same Node.
+    Links between Groups and Nodes created with Node::linked_list have it set already.
 
-    Size increases drawing time with a squared factor.
-     */
-    fn draw(&self, mut image: IW, mut offset: Coordinate, size: u32) -> IW {
-        let (mut from, mut to) = self.min_max();
-        if !self.is_connected() || from == to {
-            return image;
-        }
-        let s = coordinate!(size / 2);
-        offset += s;
-        from += offset;
-        to += offset;
-
-        for i in 0..size {
-            for j in 0..size {
-                let add = coordinate!(j, i) - s - s;
-                let col = (size - i) as u8 * consts::DEFAULT_SHADE as u8;
-                let plot = match self.style {
-                    EdgeStyle::Direct => {
-                        tools::plot_type(from + add, to + add, &tools::plot_bresenham)
-                    }
-                    EdgeStyle::Straight => {
-                        tools::plot_type(from + add, to + add, &tools::plot_rectangle)
-                    }
-                    EdgeStyle::Ellipse => {
-                        tools::plot_type(from + add, to + add, &tools::plot_ellipse)
-                    }
-                };
-                let _ = plot
-                    .iter()
-                    .map(|c| image.put(c, image::Rgba([col, col, col, u8::max_value()])))
-                    .collect::<Vec<_>>();
-            }
-        }
-        image
-    }
-}
 
-impl Group {
-    /**
-    Constructs a new Group.
+    ## Examples
 
-    name is converted internally as a hash.
-     */
+    ```
+    # #[macro_use] use pathtracer::*;
+    # fn main() -> std::io::Result<()> {
+    let mut b = node!(30, 40);
+    b.radius = Some(12);
+    let mut a = node!();
+    a.link(&b);
+    a.hl_mut(0)?.to_size(b.size());
+    assert_eq!(a.hl(0)?.to_size, Some(12));
+    # Ok(())
+    # }
+    ```
+     */
+    pub fn to_size(&mut self, size: u32) {
+        self.to_size = Some(size);
+    }
+
+    /**
+    Sets a label describing the edge.
+
+    The label is drawn halfway along the edge, To show the weight use it as the label.
+     */
+    pub fn label(&mut self, label: &str) {
+        self.label = Some(label.to_string());
+    }
+
+    /**
+    Draws an arrowhead of the given size at the to end of the edge.
+
+    Undirected edges are drawn without an arrowhead.
+
+
+    ## Examples
+
+    ```
+    # #[macro_use] use pathtracer::*;
+    # fn main() -> std::io::Result<()> {
+    let b = node!(30, 40);
+    let mut a = node!();
+    a.link(&b);
+    a.hl_mut(0)?.arrow(8);
+    assert_eq!(a.hl(0)?.arrow, Some(8));
+    # Ok(())
+    # }
+    ```
+     */
+    pub fn arrow(&mut self, size: u32) {
+        self.arrow = Some(size);
+    }
+
+    /**
+    Sets the color the edge and its arrowhead are drawn with, The alpha channel sets the transparency.
+
+
+    ## Examples
+
+    Colors the critical edge red and draws it wider than the rest.
+
+    ```
+    # #[macro_use] use pathtracer::*;
+    # fn main() -> std::io::Result<()> {
+    let b = node!(30, 40);
+    let mut a = node!();
+    a.link(&b);
+    let link = a.hl_mut(0)?;
+    link.color(image::Rgba([255, 0, 0, 255]));
+    link.width(4);
+    # Ok(())
+    # }
+    ```
+     */
+    pub fn color(&mut self, color: image::Rgba<u8>) {
+        self.color = color;
+    }
+
+    /**
+    Sets how many positions wide the edge is drawn.
+     */
+    pub fn width(&mut self, width: u32) {
+        self.width = width;
+    }
+
+    /**
+    Returns the cost of travelling the edge between two positions.
+
+    Uses the weight if set, otherwise the distance between the positions.
+     */
+    pub fn cost(&self, from: Coordinate, to: Coordinate) -> u32 {
+        self.weight
+            .unwrap_or_else(|| coordinate::distance(from, to))
+    }
+
+    /**
+    Checks if the HL has two endpoint hashes.
+
+
+    ## Examples
+
+    ```
+    # #[macro_use] use pathtracer::*;
+    # fn main() -> std::io::Result<()> {
+    let b = cluster!();
+    let mut a = node!();
+    a.link(&b);
+    assert!(a.hl(0)?.is_connected());
+    # Ok(())
+    # }
+    ```
+     */
+    pub fn is_connected(&self) -> bool {
+        self.f != 0 && self.t != 0
+    }
+
+    /**
+    Returns the corner points the edge passes through from start to end, matching the pixels drawn by the EdgeStyle.
+     */
+    fn waypoints(&self, from: Coordinate, to: Coordinate) -> Vec<Coordinate> {
+        // The plot functions always draw from left to right.
+        let (a, b) = if to.x < from.x {
+            (to, from)
+        } else {
+            (from, to)
+        };
+        let mut points = match self.style {
+            EdgeStyle::Direct => vec![a, b],
+            EdgeStyle::Straight => vec![a, coordinate!(b.x, a.y), b],
+            EdgeStyle::Ellipse => tools::ellipse_points(a, b),
+            EdgeStyle::Bezier => {
+                tools::bezier_points(&tools::bezier_control(a, b), tools::bezier_steps(a, b))
+            }
+            EdgeStyle::Orthogonal => tools::orthogonal_points(a, b),
+        };
+        if a != from {
+            points.reverse();
+        }
+        points
+    }
+
+    /**
+    Returns the points the edge passes through in world space, from the from end through the via positions to the to end.
+     */
+    fn world_waypoints(&self) -> Vec<Point> {
+        let mut ends = vec![self.from.unwrap_or_default()];
+        ends.extend(&self.via);
+        ends.push(self.to.unwrap_or_default());
+        ends.windows(2).fold(Vec::new(), |mut acc, pair| {
+            let mut points = self.stretch(pair[0], pair[1]);
+            if !acc.is_empty() {
+                points.remove(0);
+            }
+            acc.append(&mut points);
+            acc
+        })
+    }
+
+    /**
+    Returns the waypoints between the whole positions of from and to, stretched to the exact positions.
+
+    Each axis is stretched between the ends, an axis without span follows the progress along the other one.
+     */
+    fn stretch(&self, from: Point, to: Point) -> Vec<Point> {
+        let (a, b) = (Coordinate::from(from), Coordinate::from(to));
+        let progress =
+            |v: i32, a: i32, b: i32| Some(f64::from(v - a) / f64::from(b - a)).filter(|_| a != b);
+        let stretch = |v: i32, a: i32, b: i32, from: f64, to: f64, t: f64| {
+            f64::from(v) + (from - f64::from(a)) * (1.0 - t) + (to - f64::from(b)) * t
+        };
+        self.waypoints(a, b)
+            .into_iter()
+            .map(|c| {
+                let (tx, ty) = (progress(c.x, a.x, b.x), progress(c.y, a.y, b.y));
+                let (tx, ty) = (tx.or(ty).unwrap_or(0.0), ty.or(tx).unwrap_or(0.0));
+                Point::new(
+                    stretch(c.x, a.x, b.x, from.x, to.x, tx),
+                    stretch(c.y, a.y, b.y, from.y, to.y, ty),
+                )
+            })
+            .collect()
+    }
+
+    /**
+    Returns the corners of the arrowhead pointing at the last point.
+
+    The tip is pulled back by the size of the Node drawn there, so it is not hidden by it.
+     */
+    fn arrowhead(&self, points: &[Point], target: u32) -> Option<[Point; 3]> {
+        let size = f64::from(self.arrow.filter(|_| !self.undirected)?);
+        let to = *points.last()?;
+        let prev = points.iter().rev().find(|x| **x != to)?;
+
+        let (dx, dy) = (to.x - prev.x, to.y - prev.y);
+        let length = dx.hypot(dy);
+        let (ux, uy) = (dx / length, dy / length);
+        let gap = f64::from(target);
+        let tip = Point::new(to.x - ux * gap, to.y - uy * gap);
+        let base = Point::new(tip.x - ux * size, tip.y - uy * size);
+        let half = size / 2.0;
+        Some([
+            tip,
+            Point::new(base.x - uy * half, base.y + ux * half),
+            Point::new(base.x + uy * half, base.y - ux * half),
+        ])
+    }
+
+    /**
+    Returns the size of the Node at the to end, or the default size if it is not known.
+     */
+    fn target_size(&self) -> u32 {
+        self.to_size
+            .unwrap_or_else(|| u32::from(consts::DEFAULT_SIZE))
+    }
+
+    /**
+    Returns the top-left and bottom-right corner of the arrowhead, if the HL has one.
+     */
+    fn arrow_area(&self) -> Option<(Coordinate, Coordinate)> {
+        let (from, to) = self.min_max();
+        if !self.is_connected() || from == to {
+            return None;
+        }
+        let arrowhead = self.arrowhead(&self.world_waypoints(), self.target_size())?;
+        let arrowhead = tools::round(&arrowhead);
+        Some(
+            arrowhead
+                .iter()
+                .fold((arrowhead[0], arrowhead[0]), |area, c| {
+                    coordinate::union(area, (*c, *c))
+                }),
+        )
+    }
+
+    /**
+    Returns the top-left and bottom-right corner of the label, if the HL has one.
+     */
+    fn label_area(&self) -> Option<(Coordinate, Coordinate)> {
+        let label = self.label.as_ref()?;
+        let (from, to) = self.min_max();
+        if !self.is_connected() || from == to {
+            return None;
+        }
+        let size = map::font::text_size(label);
+        let points = tools::round(&self.world_waypoints());
+        let min = tools::halfway(&points) - coordinate!(size.x / 2, size.y / 2);
+        Some((min, min + size))
+    }
+
+    /**
+    Draws the label of the HL halfway along the edge.
+     */
+    fn draw_label<C: Canvas>(&self, mut canvas: C, offset: Point) -> C {
+        if let (Some(label), Some((min, _))) = (&self.label, self.label_area()) {
+            canvas.text(Point::from(min) + offset, label, consts::DEFAULT_RGBA);
+        }
+        canvas
+    }
+
+    /**
+    Draws the HL on a Canvas.
+
+    Will not draw the Edge if it is not connected, or if the the HL's from and to connections are the same Node.
+
+    The width increases drawing time with a squared factor.
+     */
+    fn draw<C: Canvas>(&self, mut canvas: C, offset: Point) -> C {
+        let (from, to) = self.min_max();
+        if !self.is_connected() || from == to {
+            return canvas;
+        }
+        let points: Vec<Point> = self
+            .world_waypoints()
+            .into_iter()
+            .map(|p| p + offset)
+            .collect();
+        canvas.line(&points, self.color, self.width);
+        if let Some(arrowhead) = self.arrowhead(&points, self.target_size()) {
+            canvas.polygon(&arrowhead, self.color);
+        }
+        canvas
+    }
+}
+
+impl Group {
+    /**
+    Constructs a new Group.
+
+    name is converted internally as a hash.
+     */
     pub fn new(name: &str, coordinates: Coordinate) -> Self {
         Group {
             settings: Node::new(name, coordinates),
@@ -1128,6 +2413,26 @@ impl Group {
         self.settings.radius = Some(radius);
     }
 
+    /**
+    Sets the Shape the nodes of the group are drawn with, unless they have a Shape of their own.
+
+
+    ## Examples
+
+    ```
+    # #[macro_use] use pathtracer::*;
+    # fn main() {
+    let mut group = cluster!();
+    group.shape(Shape::Hexagon);
+    group.add(10);
+    let image = Map::new().map(&[group]);
+    # }
+    ```
+     */
+    pub fn shape(&mut self, shape: Shape) {
+        self.settings.shape = Some(shape);
+    }
+
     /**
     Retrieves the nodes drawing in the group. Positions are relative to the group.
 
@@ -1217,7 +2522,7 @@ impl Group {
     # fn main() {
     # let mut group = cluster!();
     # group.add(10);
-    group.each(&|node: &mut Node| node.geo = coordinate!());
+    group.each(&|node: &mut Node| node.set_world(Point::default()));
     # }
     ```
      */
@@ -1275,6 +2580,71 @@ impl Group {
         false
     }
 
+    /**
+    Returns the value stored for a metadata key of the Group.
+
+    Metadata is set through the group meta data node.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let mut group = Group::new("Servers", Coordinate::new(0, 0));
+    group.set().set_meta("rack", "4");
+    assert_eq!(group.name(), Some("Servers"));
+    assert_eq!(group.meta("rack"), Some("4"));
+    ```
+     */
+    pub fn meta(&self, key: &str) -> Option<&str> {
+        self.settings.meta(key)
+    }
+
+    /**
+    Returns the text label drawn next to the Group.
+     */
+    pub fn label(&self) -> Option<&str> {
+        self.settings.label()
+    }
+
+    /**
+    Sets the text label drawn next to the Group, An empty text removes the label.
+
+    The label is placed relative to the area the nodes of the Group occupy.
+     */
+    pub fn set_label(&mut self, text: &str, placement: Placement) {
+        self.settings.set_label(text, placement);
+    }
+
+    /**
+    Returns the area the nodes occupy, without the label of the Group.
+     */
+    fn nodes_min_max(&self) -> (Coordinate, Coordinate) {
+        let mut min = coordinate!(0, 0);
+        let mut max = coordinate!(0, 0);
+        for node in &self.nodes {
+            let (min2, max2) = node.min_max();
+            max.x = std::cmp::max(max.x, max2.x);
+            min.x = std::cmp::min(min.x, min2.x);
+            max.y = std::cmp::max(max.y, max2.y);
+            min.y = std::cmp::min(min.y, min2.y);
+        }
+        (min + self.position(), max + self.position())
+    }
+
+    /**
+    Returns the top-left and bottom-right corner of the label, if the Group has one.
+     */
+    fn label_area(&self) -> Option<(Coordinate, Coordinate)> {
+        let label = self.settings.label.as_ref()?;
+        let (min, max) = self.nodes_min_max();
+        Some(
+            self.settings
+                .placement
+                .area(label, self.position(), min.y, max.y),
+        )
+    }
+
     /**
        Sets the color of the Group.
     */
@@ -1317,7 +2687,7 @@ impl Group {
     # fn main() {
     let mut group = cluster!();
     group.new_node_min_max(50, 60);
-    assert!(group.nodes()[0].geo.lt(61));
+    assert!(group.nodes()[0].geo().lt(61));
     # }
     ```
      */
@@ -1342,8 +2712,11 @@ impl Group {
     # }
     ```
      */
-    pub fn new_simple(x: i16, y: i16) -> Self {
-        Group::new(&(x + y).to_string(), Coordinate::new(x, y))
+    pub fn new_simple(x: i32, y: i32) -> Self {
+        Group::new(
+            &(i64::from(x) + i64::from(y)).to_string(),
+            Coordinate::new(x, y),
+        )
     }
 
     /**
@@ -1363,10 +2736,18 @@ impl Group {
     ```
      */
     pub fn push(&mut self, mut node: Node) {
-        node.geo -= self.position();
+        let world = node.world() - self.world();
+        node.set_world(world);
         self.nodes.push(node);
     }
 
+    /**
+    Returns the offset the Nodes are drawn with, as they are positioned relative to the Group.
+     */
+    fn offset(&self, offset: Point) -> Point {
+        offset + self.world()
+    }
+
     /**
     Returns a dynamic radius based on the number of Nodes in the Group.
 
@@ -1408,9 +2789,9 @@ impl Group {
      */
     pub fn gen_color(&self, coordinates: Coordinate) -> image::Rgba<u8> {
         tools::range_color(
-            self.dynamic_radius() as i16,
+            self.dynamic_radius() as i32,
             self.settings.color,
-            self.settings.geo,
+            self.settings.geo(),
             coordinates,
         )
     }
@@ -1429,8 +2810,8 @@ impl Group {
     assert_eq!(groups.len(), 3);
     ```
      */
-    pub fn from_list(list: &[(i16, i16)]) -> Vec<Self> {
-        coordinate::from_list(&list, &|c, i| {
+    pub fn from_list(list: &[(i32, i32)]) -> Vec<Self> {
+        coordinate::from_list(list, &|c, i| {
             Group::new(&std::char::from_u32(65 + i as u32).unwrap().to_string(), c)
         })
     }
@@ -1450,18 +2831,94 @@ impl Group {
      */
     pub fn link(&mut self, other: &Group) {
         self.settings.link(&other.settings);
+        if let Some(link) = self.settings.links.last_mut() {
+            link.to_size(other.size());
+        }
     }
 }
 
-impl<T: Draw + Hash + std::marker::Copy> Network<T> {
-    pub fn new(mut elements: Vec<T>) -> Self {
-        let mut hash_map: [Option<T>; consts::NETWORK_REM] = [None; consts::NETWORK_REM];
-        while !elements.is_empty() {
-            let e = elements.remove(0);
-            hash_map[(e.hash() as usize % consts::NETWORK_REM)] = Some(e);
+impl<T: Draw + Hash> Network<T> {
+    /**
+    Constructs a Network from a list of elements.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    # fn main() -> Result<(), Error> {
+    let nodes = Node::from_list(&[(0, 0), (10, 10)]);
+    let network = Network::new(nodes)?;
+    assert_eq!(network.len(), 2);
+    # Ok(())
+    # }
+    ```
+
+    Elements with the same name can not be told apart and are rejected.
+
+    ```
+    # use pathtracer::*;
+    let nodes = vec![Node::new("A", Coordinate::new(0, 0)); 2];
+    assert!(Network::new(nodes).is_err());
+    ```
+
+
+    ## Errors
+
+    Error::Duplicate if two elements share the same hash.
+     */
+    pub fn new(elements: Vec<T>) -> error::Result<Self> {
+        let mut network = Network {
+            hash_map: std::collections::HashMap::with_capacity(elements.len()),
+        };
+        for element in elements {
+            network.insert(element)?;
         }
+        Ok(network)
+    }
 
-        Network { hash_map }
+    /**
+    Adds an element to the Network.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    # fn main() -> std::io::Result<()> {
+    let mut network = Network::new(Node::from_list(&[(0, 0)]))?;
+    network.insert(Node::new("B", Coordinate::new(10, 10)))?;
+    assert!(network.insert(Node::new("B", Coordinate::new(20, 20))).is_err());
+    # Ok(())
+    # }
+    ```
+
+
+    ## Errors
+
+    Error::Duplicate if an element with the same hash already exists in the Network.
+     */
+    pub fn insert(&mut self, element: T) -> error::Result<()> {
+        let hash = element.hash();
+        if self.hash_map.contains_key(&hash) {
+            return Err(Error::Duplicate(hash));
+        }
+        self.hash_map.insert(hash, element);
+        Ok(())
+    }
+
+    /**
+    Returns the number of elements in the Network.
+     */
+    pub fn len(&self) -> usize {
+        self.hash_map.len()
+    }
+
+    /**
+    Returns true if the Network holds no elements.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.hash_map.is_empty()
     }
 }
 
@@ -1472,10 +2929,24 @@ impl Map {
     Creates a new map, no min_max are intially required and are generated automatically when calling Map::map.
      */
     pub fn new() -> Self {
-        Map {
-            image: None,
-            add: coordinate!(),
-        }
+        Map::empty()
+    }
+
+    /**
+    Creates a new map with an image of the given width and height, See Map::size.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let nodes = Node::from_list(&[(0, 0), (500, 250)]);
+    let image = Map::with_size(100, 100).map(&nodes).consume();
+    assert_eq!(image.dimensions(), Coordinate::new(100, 100));
+    ```
+     */
+    pub fn with_size(width: u32, height: u32) -> Self {
+        Map::new().size(width, height)
     }
 
     /**
@@ -1497,18 +2968,372 @@ impl Map {
     ```
      */
     pub fn save(self, path: &std::path::Path) -> Result<(), std::io::Error> {
-        self.image.unwrap().image().save(path)
+        self.consume().image().save(path)
+    }
+}
+
+impl Map<map::svg::Svg> {
+    /**
+    Creates a new map which draws on to a svg document instead of an image.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    # use std::path::Path;
+    # fn main() -> std::io::Result<()> {
+    let nodes = Node::linked_list(Node::from_list(&[(0, 0), (10, 10)]));
+    Map::svg()
+    .map_shape(&nodes, &Shape::Circle)
+    .save(Path::new("/tmp/example.svg"))?;
+    # Ok(())
+    # }
+    ```
+     */
+    pub fn svg() -> Self {
+        Map::empty()
+    }
+
+    /**
+    Saves the svg document to disk at the given Path.
+     */
+    pub fn save(self, path: &std::path::Path) -> Result<(), std::io::Error> {
+        Ok(self.consume().save(path)?)
+    }
+}
+
+impl<C: Canvas> Default for Map<C> {
+    fn default() -> Self {
+        Map::empty()
+    }
+}
+
+impl<C: Canvas> Map<C> {
+    /**
+    Creates a new map drawing on to the Canvas C, which is created when first mapping elements.
+     */
+    pub fn empty() -> Self {
+        Map {
+            canvas: None,
+            add: coordinate!(),
+            labels: true,
+            anti_alias: false,
+            mode: BlendMode::default(),
+            size: None,
+            margin: None,
+            viewport: None,
+            scale: ScaleMode::default(),
+            transform: map::view::Transform::new(),
+            placement: map::view::Transform::new(),
+            projection: None,
+            origin: coordinate!(),
+            view: map::view::View::default(),
+        }
+    }
+
+    /**
+    Enables or disables anti-aliased lines and shapes, They are disabled by default.
+
+    Anti-aliased edges are blended with what is already drawn instead of overwriting it.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let nodes = Node::linked_list(Node::from_list(&[(0, 0), (40, 10)]));
+    let image = Map::new().anti_alias(true).map_shape(&nodes, &Shape::Circle).consume();
+    assert!(image.anti_aliased());
+    ```
+     */
+    pub fn anti_alias(mut self, enabled: bool) -> Self {
+        self.anti_alias = enabled;
+        if let Some(canvas) = &mut self.canvas {
+            canvas.set_anti_aliased(enabled);
+        }
+        self
+    }
+
+    /**
+    Sets how colors are combined with what is already drawn, Source-over compositing is used by default.
+
+
+    ## Examples
+
+    Overlapping nodes grow brighter instead of hiding each other.
+
+    ```
+    # use pathtracer::*;
+    let mut group = Group::new_simple(0, 0);
+    group.add(200);
+    Map::new().blend_mode(BlendMode::Add).map(&[group]);
+    ```
+     */
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.mode = mode;
+        if let Some(canvas) = &mut self.canvas {
+            canvas.set_blend_mode(mode);
+        }
+        self
+    }
+
+    /**
+    Sets the width and height of the canvas, The drawn area is scaled to fit using the ScaleMode.
+
+    Must be set before the first elements are mapped.
+
+
+    ## Panics
+
+    If elements were already mapped.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let nodes = Node::from_list(&[(0, 0), (30, 60)]);
+    let svg = Map::svg().size(200, 100).map(&nodes).consume();
+    assert_eq!(svg.dimensions(), Coordinate::new(200, 100));
+    ```
+     */
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.before_mapping("size");
+        self.size = Some(coordinate!(width, height));
+        self
+    }
+
+    /**
+    Sets the number of canvas positions left empty around the drawn area.
+
+    Replaces the padding added around the elements by default, Must be set before the first elements are mapped.
+
+
+    ## Panics
+
+    If elements were already mapped.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let nodes = Node::from_list(&[(0, 0), (30, 60)]);
+    let image = Map::new().margin(10).map(&nodes).consume();
+    assert_eq!(image.dimensions(), Coordinate::new(58, 88));
+    ```
+     */
+    pub fn margin(mut self, margin: u32) -> Self {
+        self.before_mapping("margin");
+        self.margin = Some(margin);
+        self
+    }
+
+    /**
+    Sets the area between min and max that is drawn, instead of the area the first elements occupy.
+
+    Elements outside the viewport are cut off, Must be set before the first elements are mapped.
+
+
+    ## Panics
+
+    If elements were already mapped.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let nodes = Node::from_list(&[(0, 0), (300, 300)]);
+    let image = Map::new()
+        .viewport(Coordinate::new(0, 0), Coordinate::new(100, 50))
+        .map(&nodes)
+        .consume();
+    assert_eq!(image.dimensions(), Coordinate::new(100, 50));
+    ```
+     */
+    pub fn viewport(mut self, min: Coordinate, max: Coordinate) -> Self {
+        self.before_mapping("viewport");
+        self.viewport = Some((min, max));
+        self
+    }
+
+    /**
+    Sets how the drawn area is scaled when the Map has a fixed size, It is fit inside by default.
+
+
+    ## Panics
+
+    If elements were already mapped.
+     */
+    pub fn scale_mode(mut self, mode: ScaleMode) -> Self {
+        self.before_mapping("scale_mode");
+        self.scale = mode;
+        self
     }
 
     /**
-    Consumes the Map and returns the ImageWrapper.
+    Sets the transform from world positions to drawn positions, applied only when drawing.
+
+    The canvas is sized and the ScaleMode applied to the transformed area, Must be set before the first elements are mapped.
+
+
+    ## Panics
+
+    If elements were already mapped.
+
+
+    ## Examples
+
+    Draw the nodes with y growing upwards, at twice the size.
+
+    ```
+    # use pathtracer::{map::view::Transform, *};
+    let nodes = Node::from_list(&[(0, 0), (40, 20)]);
+    let transform = Transform::new().flip_y().scale(2.0, 2.0);
+    let image = Map::new().transform(transform).map(&nodes).consume();
+    assert_eq!(image.dimensions(), Coordinate::new(100, 60));
+    ```
      */
-    pub fn consume(self) -> IW {
-        self.image.unwrap()
+    pub fn transform(mut self, transform: map::view::Transform) -> Self {
+        self.before_mapping("transform");
+        self.transform = transform;
+        self
     }
 
     /**
-    Maps any struct that has implemented Draw, on to an ImageBuffer.
+    Sets how world positions created from latitude and longitude are drawn, See Node::from_lat_lon.
+
+    The canvas is sized to the elements alone, instead of also containing 0 0, Must be set before the first elements are mapped.
+    Without a size, the elements are scaled so the longer side of the canvas is consts::DEFAULT_PROJECTED_SIZE positions.
+    Elements created from latitude and longitude are sized the same way without a projection, drawn as Projection::Equirectangular.
+
+
+    ## Panics
+
+    If elements were already mapped.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let nodes = [
+        Node::from_lat_lon("London", 51.5074, -0.1278),
+        Node::from_lat_lon("Paris", 48.8566, 2.3522),
+    ];
+    let image = Map::new().projection(Projection::WebMercator).map(&nodes).consume();
+    let size = image.dimensions();
+    assert_eq!(size.x.max(size.y), 1000);
+    ```
+     */
+    pub fn projection(mut self, projection: Projection) -> Self {
+        self.before_mapping("projection");
+        self.projection = Some(projection);
+        self
+    }
+
+    /**
+    Panics if elements were already mapped, as the setting is only used for the canvas created for the first elements.
+     */
+    fn before_mapping(&self, setting: &str) {
+        if self.canvas.is_some() {
+            panic!(
+                "Map::{} must be set before the first elements are mapped",
+                setting
+            );
+        }
+    }
+
+    /**
+    Creates the canvas for the first elements mapped, and the View that places them on it.
+
+    Absolute views keep the elements at their own positions, instead of moving the drawn area to the top-left corner.
+     */
+    fn create_view<T: Draw + Location + Hash + MinMax>(&mut self, element: &[T], absolute: bool) {
+        let geographic = self.projection.is_some() || element.iter().any(Location::geographic);
+        let (min, max) = match self.viewport {
+            Some(viewport) => viewport,
+            None if self.margin.is_some() => map::bounds(element, !geographic),
+            None => map::min_max(element, !geographic),
+        };
+
+        // Projections keep the order of positions, so the projected corners contain the area.
+        let projection = self.projection.unwrap_or_default();
+        let (pmin, pmax) = (
+            Coordinate::from(projection.project(Point::from(min))),
+            Coordinate::from(projection.project(Point::from(max))),
+        );
+        let (low, high) = self.transform.bounds(pmin, pmax);
+        self.placement = if absolute {
+            self.transform
+        } else {
+            self.transform
+                .translate(-f64::from(low.x), -f64::from(low.y))
+        };
+        self.origin = min;
+
+        let margin = coordinate!(self.margin.unwrap_or(0));
+        let area = high - low;
+        let size = match self.size {
+            Some(size) => size,
+            // Geographic positions are in metres, so the area is scaled down to fit a canvas.
+            None if geographic => {
+                let longest = f64::from(area.x.max(area.y).max(1));
+                let scale = f64::from(consts::DEFAULT_PROJECTED_SIZE) / longest;
+                let scaled = |v: i32| (f64::from(v) * scale).round();
+                coordinate!(scaled(area.x), scaled(area.y)) + margin + margin
+            }
+            None => area + margin + margin,
+        };
+        self.view = map::view::View::fit(area, size - margin - margin, margin, self.scale);
+        self.add = Coordinate::new(-min.x, -min.y);
+        self.create_canvas(size);
+    }
+
+    /**
+    Creates the canvas with the given size, using the settings of the Map.
+     */
+    fn create_canvas(&mut self, size: Coordinate) {
+        let mut canvas = C::blank(size.x as u32, size.y as u32);
+        canvas.set_anti_aliased(self.anti_alias);
+        canvas.set_blend_mode(self.mode);
+        self.canvas = Some(canvas);
+    }
+
+    /**
+    Enables or disables drawing the labels of Nodes and Groups, They are drawn by default.
+
+    Labels are drawn after the elements, so they are not hidden by elements mapped in the same call.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let mut node = Node::new("A", Coordinate::new(0, 0));
+    node.set_label("Home", Placement::Below);
+    let nodes = [node];
+    let with = Map::new().map(&nodes).consume();
+    let without = Map::new().labels(false).map(&nodes).consume();
+    assert_ne!(with.image().to_vec(), without.image().to_vec());
+    ```
+     */
+    pub fn labels(mut self, enabled: bool) -> Self {
+        self.labels = enabled;
+        self
+    }
+
+    /**
+    Consumes the Map and returns the Canvas.
+     */
+    pub fn consume(self) -> C {
+        self.canvas.unwrap()
+    }
+
+    /**
+    Maps any struct that has implemented Draw, on to the Canvas.
 
 
     ## Examples
@@ -1524,7 +3349,7 @@ impl Map {
     ```
      */
     pub fn map<T: Draw + Location + Hash + MinMax>(self, element: &[T]) -> Self {
-        self.map_filter(&element, &|_| true)
+        self.map_filter(element, &|_| true)
     }
 
     /**
@@ -1535,33 +3360,62 @@ impl Map {
         element: &[T],
         filter: &dyn Fn(&T) -> bool,
     ) -> Self {
-        self.map_params(&element, &filter, &Shape::Square)
+        self.map_params(element, &filter, &Shape::Square)
     }
 
     /**
     Maps the elements with a specified shape struct.
+
+    The shape is only used for elements that do not have a Shape of their own.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let mut nodes = Node::from_list(&[(0, 0), (20, 20)]);
+    nodes[0].shape = Some(Shape::Star);
+    let svg = Map::svg().map_shape(&nodes, &Shape::Square).consume();
+    assert!(svg.elements()[0].starts_with("<polygon"));
+    assert!(svg.elements()[1].starts_with("<rect"));
+    ```
      */
     pub fn map_shape<T: Draw + Location + Hash + MinMax>(
         self,
         element: &[T],
         shape: &Shape,
     ) -> Self {
-        self.map_params(&element, &|_| true, shape)
+        self.map_params(element, &|_| true, shape)
     }
 
     /**
     Maps the elements without stabalizing the positions on the canvas.
+
+    The canvas is sized and the settings applied as with Map::map, but the drawn area is not moved to the top-left corner.
+    Elements keep their own positions, so those left of or above 0 0 are cut off.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::*;
+    let nodes = Node::from_list(&[(-20, 0), (40, 40)]);
+    let svg = Map::svg().map_absolute(&nodes).consume();
+    assert!(svg.elements()[0].starts_with("<rect x=\"-20\" y=\"0\""));
+    assert!(svg.elements()[1].starts_with("<rect x=\"40\" y=\"40\""));
+    ```
      */
     pub fn map_absolute<T: Draw + Location + Hash + MinMax>(mut self, element: &[T]) -> Self {
-        if self.image.is_none() {
-            let (image, _) = map::gen_map(&element);
-            self.image = Some(IW { img: image });
+        if self.canvas.is_none() {
+            self.create_view(element, true);
         }
         self.map(element)
     }
 
     /**
     Maps the elements but with all added parameters.
+
+    The shape is a fallback for elements without a Shape of their own.
      */
     pub fn map_params<T: Draw + Location + Hash + MinMax>(
         mut self,
@@ -1569,19 +3423,30 @@ impl Map {
         filter: &dyn Fn(&T) -> bool,
         shape: &Shape,
     ) -> Self {
-        if self.image.is_none() {
-            let (image, add) = map::gen_map(&element);
-            self.image = Some(IW { img: image });
-            self.add = add;
+        if self.canvas.is_none() {
+            self.create_view(element, false);
         }
 
         let add = self.add;
-        self.image = Some(
-            element
+        let viewed = map::view::Viewed {
+            canvas: self.canvas.unwrap(),
+            origin: self.origin,
+            projection: self.projection.unwrap_or_default(),
+            transform: self.placement,
+            view: self.view,
+        };
+        let mut canvas = element
+            .iter()
+            .filter(|x| filter(x))
+            .fold(viewed, |canvas, x| x.draw(canvas, Point::from(add), shape));
+
+        if self.labels {
+            canvas = element
                 .iter()
                 .filter(|x| filter(x))
-                .fold(self.image.unwrap(), |img, x| x.draw(img, add, shape)),
-        );
+                .fold(canvas, |canvas, x| x.draw_label(canvas, Point::from(add)));
+        }
+        self.canvas = Some(canvas.canvas);
         self
     }
 }
@@ -1597,12 +3462,12 @@ impl Network<Node> {
     # use pathtracer::*;
     let nodes = Node::from_list(&[(0, 0), (10, 10), (20, 20), (30, 30)]);
     let mut nodes = Node::linked_list(nodes);
-    let path = Network::new(nodes).path("A", "D").unwrap();
+    let path = Network::new(nodes).unwrap().path("A", "D").unwrap();
     assert_eq!(path.len(), 4);
     ```
      */
-    pub fn path<'a>(&'a self, a: &str, b: &str) -> std::io::Result<Vec<Node>> {
-        let mut path = map::network::path(self, b, a, &map::network::path_shortest_leg)?;
+    pub fn path(&self, a: &str, b: &str) -> error::Result<Vec<Node>> {
+        let mut path = map::network::path(self, b, a, &map::network::dijkstra)?;
         path.reverse();
         Ok(path)
     }
@@ -1610,8 +3475,8 @@ impl Network<Node> {
     /**
     Mimics path behaviour but works in reverse, Meaning stepping back in the links.
      */
-    pub fn path_rev<'a>(&'a self, a: &str, b: &str) -> std::io::Result<Vec<Node>> {
-        map::network::path(self, a, b, &map::network::path_shortest_leg)
+    pub fn path_rev(&self, a: &str, b: &str) -> error::Result<Vec<Node>> {
+        map::network::path(self, a, b, &map::network::dijkstra)
     }
 
     /**
@@ -1623,7 +3488,7 @@ impl Network<Node> {
     ```
     # use pathtracer::*;
     let nodes = Node::from_list(&[(0, 0), (10, 10), (20, 20), (30, 30), (40, 40)]);
-    let network = Network::new(nodes.clone());
+    let network = Network::new(nodes.clone()).unwrap();
     assert!(network.get("A").is_some());
     assert!(network.get("F").is_none());
     ```
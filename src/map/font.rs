@@ -0,0 +1,242 @@
+/*!
+  Embedded bitmap font used to draw text on canvases that can only put pixels.
+
+  Every glyph is 3 pixels wide and 5 pixels tall, lowercase letters are drawn as uppercase.
+*/
+
+use super::super::*;
+
+/**
+Width of a glyph in pixels.
+*/
+pub const WIDTH: u32 = 3;
+
+/**
+Height of a glyph in pixels.
+*/
+pub const HEIGHT: u32 = 5;
+
+/**
+Empty pixels between two glyphs.
+*/
+pub const SPACING: u32 = 1;
+
+/**
+Returns the width and height in pixels the text occupies when drawn.
+
+
+## Examples
+
+```
+# use pathtracer::*;
+assert_eq!(map::font::text_size("AB"), Coordinate::new(7, 5));
+assert_eq!(map::font::text_size(""), Coordinate::new(0, 0));
+```
+*/
+pub fn text_size(text: &str) -> Coordinate {
+    let count = text.chars().count() as u32;
+    if count == 0 {
+        return coordinate!();
+    }
+    coordinate!(count * (WIDTH + SPACING) - SPACING, HEIGHT)
+}
+
+/**
+Returns the positions of the pixels that make up the text, relative to its top-left corner.
+*/
+pub fn text_points(text: &str) -> Vec<Coordinate> {
+    let mut points = Vec::new();
+    for (i, c) in text.chars().enumerate() {
+        let x = i as u32 * (WIDTH + SPACING);
+        for (y, row) in glyph(c).iter().enumerate() {
+            for dx in 0..WIDTH {
+                if row >> (WIDTH - 1 - dx) & 1 == 1 {
+                    points.push(coordinate!(x + dx, y));
+                }
+            }
+        }
+    }
+    points
+}
+
+/**
+Returns the rows of the glyph for the character, each row holds one bit per pixel.
+
+Characters that are not printable ascii are drawn as a question mark.
+*/
+pub fn glyph(c: char) -> [u8; 5] {
+    let c = c.to_ascii_uppercase();
+    match c {
+        ' '..='`' => GLYPHS[c as usize - ' ' as usize],
+        '{'..='~' => GLYPHS[c as usize - '{' as usize + 65],
+        _ => GLYPHS['?' as usize - ' ' as usize],
+    }
+}
+
+/**
+Glyphs for the printable ascii characters, without the lowercase letters.
+*/
+const GLYPHS: [[u8; 5]; 69] = [
+    // ' '
+    [0b000, 0b000, 0b000, 0b000, 0b000],
+    // '!'
+    [0b010, 0b010, 0b010, 0b000, 0b010],
+    // '"'
+    [0b101, 0b101, 0b000, 0b000, 0b000],
+    // '#'
+    [0b101, 0b111, 0b101, 0b111, 0b101],
+    // '$'
+    [0b011, 0b110, 0b010, 0b011, 0b110],
+    // '%'
+    [0b101, 0b001, 0b010, 0b100, 0b101],
+    // '&'
+    [0b010, 0b101, 0b010, 0b101, 0b011],
+    // '\''
+    [0b010, 0b010, 0b000, 0b000, 0b000],
+    // '('
+    [0b001, 0b010, 0b010, 0b010, 0b001],
+    // ')'
+    [0b100, 0b010, 0b010, 0b010, 0b100],
+    // '*'
+    [0b000, 0b101, 0b010, 0b101, 0b000],
+    // '+'
+    [0b000, 0b010, 0b111, 0b010, 0b000],
+    // ','
+    [0b000, 0b000, 0b000, 0b010, 0b100],
+    // '-'
+    [0b000, 0b000, 0b111, 0b000, 0b000],
+    // '.'
+    [0b000, 0b000, 0b000, 0b000, 0b010],
+    // '/'
+    [0b001, 0b001, 0b010, 0b100, 0b100],
+    // '0'
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    // '1'
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    // '2'
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    // '3'
+    [0b111, 0b001, 0b011, 0b001, 0b111],
+    // '4'
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    // '5'
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    // '6'
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    // '7'
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    // '8'
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    // '9'
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+    // ':'
+    [0b000, 0b010, 0b000, 0b010, 0b000],
+    // ';'
+    [0b000, 0b010, 0b000, 0b010, 0b100],
+    // '<'
+    [0b001, 0b010, 0b100, 0b010, 0b001],
+    // '='
+    [0b000, 0b111, 0b000, 0b111, 0b000],
+    // '>'
+    [0b100, 0b010, 0b001, 0b010, 0b100],
+    // '?'
+    [0b111, 0b001, 0b010, 0b000, 0b010],
+    // '@'
+    [0b010, 0b101, 0b111, 0b100, 0b011],
+    // 'A'
+    [0b010, 0b101, 0b111, 0b101, 0b101],
+    // 'B'
+    [0b110, 0b101, 0b110, 0b101, 0b110],
+    // 'C'
+    [0b011, 0b100, 0b100, 0b100, 0b011],
+    // 'D'
+    [0b110, 0b101, 0b101, 0b101, 0b110],
+    // 'E'
+    [0b111, 0b100, 0b110, 0b100, 0b111],
+    // 'F'
+    [0b111, 0b100, 0b110, 0b100, 0b100],
+    // 'G'
+    [0b011, 0b100, 0b101, 0b101, 0b011],
+    // 'H'
+    [0b101, 0b101, 0b111, 0b101, 0b101],
+    // 'I'
+    [0b111, 0b010, 0b010, 0b010, 0b111],
+    // 'J'
+    [0b001, 0b001, 0b001, 0b101, 0b010],
+    // 'K'
+    [0b101, 0b101, 0b110, 0b101, 0b101],
+    // 'L'
+    [0b100, 0b100, 0b100, 0b100, 0b111],
+    // 'M'
+    [0b101, 0b111, 0b111, 0b101, 0b101],
+    // 'N'
+    [0b110, 0b101, 0b101, 0b101, 0b101],
+    // 'O'
+    [0b010, 0b101, 0b101, 0b101, 0b010],
+    // 'P'
+    [0b110, 0b101, 0b110, 0b100, 0b100],
+    // 'Q'
+    [0b010, 0b101, 0b101, 0b110, 0b011],
+    // 'R'
+    [0b110, 0b101, 0b110, 0b101, 0b101],
+    // 'S'
+    [0b011, 0b100, 0b010, 0b001, 0b110],
+    // 'T'
+    [0b111, 0b010, 0b010, 0b010, 0b010],
+    // 'U'
+    [0b101, 0b101, 0b101, 0b101, 0b111],
+    // 'V'
+    [0b101, 0b101, 0b101, 0b101, 0b010],
+    // 'W'
+    [0b101, 0b101, 0b111, 0b111, 0b101],
+    // 'X'
+    [0b101, 0b101, 0b010, 0b101, 0b101],
+    // 'Y'
+    [0b101, 0b101, 0b010, 0b010, 0b010],
+    // 'Z'
+    [0b111, 0b001, 0b010, 0b100, 0b111],
+    // '['
+    [0b011, 0b010, 0b010, 0b010, 0b011],
+    // '\\'
+    [0b100, 0b100, 0b010, 0b001, 0b001],
+    // ']'
+    [0b110, 0b010, 0b010, 0b010, 0b110],
+    // '^'
+    [0b010, 0b101, 0b000, 0b000, 0b000],
+    // '_'
+    [0b000, 0b000, 0b000, 0b000, 0b111],
+    // '`'
+    [0b100, 0b010, 0b000, 0b000, 0b000],
+    // '{'
+    [0b001, 0b010, 0b110, 0b010, 0b001],
+    // '|'
+    [0b010, 0b010, 0b010, 0b010, 0b010],
+    // '}'
+    [0b100, 0b010, 0b011, 0b010, 0b100],
+    // '~'
+    [0b000, 0b001, 0b111, 0b100, 0b000],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyph_lookup() {
+        assert_eq!(glyph('a'), glyph('A'));
+        assert_eq!(glyph('\u{e9}'), glyph('?'));
+        assert_eq!(glyph('~'), [0b000, 0b001, 0b111, 0b100, 0b000]);
+        assert_eq!(glyph(' '), [0; 5]);
+    }
+
+    #[test]
+    fn points_stay_inside_size() {
+        let text = "Hello, World!";
+        let size = text_size(text);
+        let points = text_points(text);
+        assert!(!points.is_empty());
+        assert!(points
+            .iter()
+            .all(|p| p.x >= 0 && p.y >= 0 && p.x < size.x && p.y < size.y));
+    }
+}
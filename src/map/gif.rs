@@ -6,16 +6,16 @@
 
 use super::super::*;
 use gif::{self, *};
-use std::{fs::File, io};
+use std::fs::File;
 
-struct Cycle<'a, T: Draw + Location + Hash + MinMax + Copy> {
+struct Cycle<'a, T: Draw + Location + Hash + MinMax + Clone> {
     interval: u8,
     count: u8,
     map: Vec<T>,
     predicate: &'a dyn Fn(&T) -> T,
 }
 
-impl<'a, T: Draw + Location + Hash + MinMax + Copy> Cycle<'a, T> {
+impl<'a, T: Draw + Location + Hash + MinMax + Clone> Cycle<'a, T> {
     pub fn new(interval: u8, map: Vec<T>, predicate: &'a dyn Fn(&T) -> T) -> Self {
         Cycle {
             interval,
@@ -66,17 +66,17 @@ impl<'a> Gif<'a> {
     example/cycles.rs
 
     */
-    pub fn new(filename: &str, width: u16, height: u16) -> Self {
-        let file = File::create(filename).unwrap();
-        let mut encoder = Encoder::new(file, width, height, &[]).unwrap();
-        encoder.set(Repeat::Infinite).unwrap();
-        Gif {
+    pub fn new(filename: &str, width: u16, height: u16) -> error::Result<Self> {
+        let file = File::create(filename)?;
+        let mut encoder = Encoder::new(file, width, height, &[])?;
+        encoder.set(Repeat::Infinite)?;
+        Ok(Gif {
             encoder,
             cycles: Vec::new(),
             frames: 0,
             width,
             height,
-        }
+        })
     }
 
     /**
@@ -88,7 +88,7 @@ impl<'a> Gif<'a> {
     examples/cycles.rs
     */
     pub fn cycle(&mut self, interval: u8, map: Vec<Node>) {
-        self.cycles.push(Cycle::new(interval, map, &|x| *x));
+        self.cycles.push(Cycle::new(interval, map, &|x| x.clone()));
     }
 
     /**
@@ -133,7 +133,7 @@ impl<'a> Gif<'a> {
     /**
     Pushes a frame using a map struct.
     */
-    pub fn push(&mut self, mut map: Map) -> Result<(), io::Error> {
+    pub fn push(&mut self, mut map: Map) -> error::Result<()> {
         map = map.map(&self.advance_cycle());
         self.push_frame(&map.consume())
     }
@@ -145,7 +145,7 @@ impl<'a> Gif<'a> {
 
     If the encoder fails to write the frame to disk.
     */
-    pub fn push_frame(&mut self, image: &IW) -> Result<(), io::Error> {
+    pub fn push_frame(&mut self, image: &IW) -> error::Result<()> {
         let mut pixels: Vec<u8> = Vec::new();
         for pix in image.image().pixels() {
             for i in 0..4 {
@@ -167,8 +167,8 @@ impl<'a> Gif<'a> {
 
     This will also advance Gif cycles.
     */
-    pub fn blank(&mut self) -> Result<(), io::Error> {
-        let mut node = node!(self.width as i16 - 1, self.height as i16 - 1);
+    pub fn blank(&mut self) -> error::Result<()> {
+        let mut node = node!(self.width as i32 - 1, self.height as i32 - 1);
         node.radius = Some(0);
         self.push(Map::new().map(&[node]))
     }
@@ -178,8 +178,8 @@ impl<'a> Gif<'a> {
 mod tests {
     use super::*;
 
-    fn define(case: &dyn Fn(Gif) -> std::io::Result<()>) {
-        let gif = Gif::new("/tmp/test_gif_new.gif", 50, 50);
+    fn define(case: &dyn Fn(Gif) -> error::Result<()>) {
+        let gif = Gif::new("/tmp/test_gif_new.gif", 50, 50).unwrap();
         case(gif).unwrap();
         // This can break Travis CI. Because the file doesn't get created?
         //let _ = std::fs::remove_file("test_gif_new.gif").unwrap();
@@ -205,7 +205,7 @@ mod tests {
         define(&|mut gif| {
             gif.cycle_predicate(1, vec![node!(25, 25)], &|x| {
                 let mut x = x.clone();
-                x.geo.x += 5;
+                x.set_world(x.world() + Point::new(5.0, 0.0));
                 x
             });
 
@@ -8,8 +8,11 @@ use super::*;
 use image::Rgba;
 use std::cmp;
 
+pub mod font;
 pub mod gif;
 pub mod network;
+pub mod svg;
+pub mod view;
 
 /**
 Returns the underlaying image used for the Map struct.
@@ -17,41 +20,63 @@ Returns the underlaying image used for the Map struct.
 pub fn gen_map<T: Location + Draw + MinMax>(
     list: &[T],
 ) -> (image::ImageBuffer<Rgba<u8>, Vec<u8>>, Coordinate) {
-    let (min, max) = min_max(&list);
-    let diff = max - min;
-    let add = Coordinate::new(-min.x, -min.y);
-    let image = gen_canvas(diff.x as u32, diff.y as u32);
+    let (size, add) = gen_bounds(list);
+    let image = gen_canvas(size.x as u32, size.y as u32);
     (image, add)
 }
 
+/**
+Returns the size required to fit the list, and the offset that moves it inside that size.
+*/
+pub fn gen_bounds<T: Location + Draw + MinMax>(list: &[T]) -> (Coordinate, Coordinate) {
+    let (min, max) = min_max(list, true);
+    (max - min, Coordinate::new(-min.x, -min.y))
+}
+
 /**
 Finds the min and max of a list and returns (min, max).
 
 the min and max use the size of the Draw trait to enlarge the are the min, max occupy.
 */
-fn min_max<T: Location + Draw + MinMax>(list: &[T]) -> (Coordinate, Coordinate) {
-    let mut size: i16 = consts::DEFAULT_SIZE as i16;
-    let mut min = coordinate!();
-    let mut max = coordinate!();
-
-    for item in list {
-        size = cmp::max(size, item.size() as i16);
-        let (imin, imax) = item.min_max();
-
-        max.x = cmp::max(max.x, imax.x);
-        min.x = cmp::min(min.x, imin.x);
-        max.y = cmp::max(max.y, imax.y);
-        min.y = cmp::min(min.y, imin.y);
-    }
-
+pub fn min_max<T: Location + Draw + MinMax>(list: &[T], origin: bool) -> (Coordinate, Coordinate) {
+    let size = list.iter().fold(consts::DEFAULT_SIZE as i32, |acc, item| {
+        cmp::max(acc, item.size() as i32)
+    });
+    let (min, max) = bounds(list, origin);
     let size = coordinate!(size / 4);
     (min - size, max + size)
 }
 
+/**
+Finds the area the elements of a list occupy without any padding, Along with 0 0 if origin is true.
+
+
+## Examples
+
+```
+# use pathtracer::*;
+let nodes = Node::from_list(&[(10, 10), (50, 20)]);
+let (min, max) = map::bounds(&nodes, true);
+assert_eq!(min, Coordinate::new(0, 0));
+assert_eq!(max, Coordinate::new(54, 24));
+assert_eq!(map::bounds(&nodes, false).0, Coordinate::new(6, 6));
+```
+*/
+pub fn bounds<T: MinMax>(list: &[T], origin: bool) -> (Coordinate, Coordinate) {
+    let mut areas = list.iter().map(|item| item.min_max());
+    let first = match (origin, areas.next()) {
+        (false, Some(area)) => area,
+        (_, area) => area.map_or((coordinate!(), coordinate!()), |area| {
+            coordinate::union((coordinate!(), coordinate!()), area)
+        }),
+    };
+    areas.fold(first, coordinate::union)
+}
+
 /**
 Generates a canvas from the image crate.
 */
-fn gen_canvas(w: u32, h: u32) -> image::ImageBuffer<Rgba<u8>, Vec<u8>> {
+pub fn gen_canvas(w: u32, h: u32) -> image::ImageBuffer<Rgba<u8>, Vec<u8>> {
     image::DynamicImage::new_rgba8(w, h).to_rgba()
 }
 
@@ -75,7 +100,7 @@ mod tests {
     #[test]
     fn test_min_max() {
         let nodes = Node::from_list(&[(-50, 50), (50, -50), (0, 25), (25, 0)]);
-        let (min, max) = min_max(&nodes);
+        let (min, max) = min_max(&nodes, true);
         assert_eq!(min, Coordinate::new(-55, -55));
         assert_eq!(max, Coordinate::new(55, 55));
     }
@@ -83,8 +108,55 @@ mod tests {
     #[test]
     fn test_min_max_2() {
         let nodes = Node::from_list(&[(-9999, 50), (50, -50), (0, 25), (9999, 0)]);
-        let (min, max) = min_max(&nodes);
+        let (min, max) = min_max(&nodes, true);
         assert_eq!(min, Coordinate::new(-10004, -55));
         assert_eq!(max, Coordinate::new(10004, 55));
     }
+
+    #[test]
+    fn test_min_max_beyond_i16() {
+        let nodes = Node::from_list(&[(-100_000, 50), (250_000, 40_000)]);
+        let (min, max) = min_max(&nodes, true);
+        assert_eq!(min, Coordinate::new(-100_005, -1));
+        assert_eq!(max, Coordinate::new(250_005, 40_005));
+    }
+
+    #[test]
+    fn test_gen_bounds() {
+        let nodes = Node::from_list(&[(-50, 50), (50, -50)]);
+        let (size, add) = gen_bounds(&nodes);
+        assert_eq!(size, Coordinate::new(110, 110));
+        assert_eq!(add, Coordinate::new(55, 55));
+    }
+
+    #[test]
+    fn test_map_svg() {
+        let nodes = Node::linked_list(Node::from_list(&[(0, 0), (50, 20), (10, 40)]));
+        let svg = Map::svg().map(&nodes).consume();
+        let count = |tag| svg.elements().iter().filter(|x| x.starts_with(tag)).count();
+        assert_eq!(count("<rect"), 3);
+        assert_eq!(count("<line"), 2);
+        assert!(svg.to_string().starts_with("<svg"));
+    }
+
+    #[test]
+    fn test_map_svg_styles() {
+        let mut a = node!("A", 0, 0);
+        let b = node!("B", 40, 20);
+        a.link(&b);
+        for (style, tag) in &[
+            (EdgeStyle::Direct, "<line"),
+            (EdgeStyle::Straight, "<polyline"),
+            (EdgeStyle::Ellipse, "<polyline"),
+            (EdgeStyle::Bezier, "<polyline"),
+            (EdgeStyle::Orthogonal, "<polyline"),
+        ] {
+            a.hl_mut(0).unwrap().style(*style);
+            let svg = Map::svg()
+                .map_shape(&[a.clone(), b.clone()], &Shape::Circle)
+                .consume();
+            assert!(svg.elements()[0].starts_with(tag));
+            assert!(svg.elements()[1].starts_with("<circle"));
+        }
+    }
 }
@@ -3,7 +3,10 @@ Connects and paths between different connected Nodes.
  */
 
 use super::*;
-use std::io::{self, Error, ErrorKind};
+use std::{
+    cmp,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
 
 /**
 Weighted Node
@@ -13,31 +16,34 @@ struct WNodes {
     weight: u32,
 }
 
+/**
+Undirected links indexed by the Node they point to, Used to travel them backwards.
+ */
+type ReverseLinks<'a> = HashMap<u64, Vec<(u64, &'a HL)>>;
+
 /**
 Paths between two different points that are connected.
 
+The algorithm returns the path ordered from the goal back to the start.
+
 
 ## Errors
 
-The provided A and B don't exist in the network.
+Error::NotInNetwork if the provided A or B don't exist in the network.
 
-The path could not be found.
+Error::NoPath if the path could not be found.
  */
+#[allow(clippy::type_complexity)]
 pub fn path<'a>(
     network: &'a Network<Node>,
     a: &str,
     b: &str,
-    algorithm: &dyn Fn(&Network<Node>, Node, Node) -> io::Result<Vec<Node>>,
-) -> io::Result<Vec<Node>> {
-    let opt_goal = network.get(b);
-    let opt_start = network.get(a);
-    if opt_goal.is_none() || opt_start.is_none() {
-        Err(Error::new(
-            ErrorKind::Other,
-            "Start or Goal path does not exist in Network",
-        ))
-    } else {
-        algorithm(&network, opt_start.unwrap(), opt_goal.unwrap())
+    algorithm: &dyn Fn(&Network<Node>, Node, Node) -> error::Result<Vec<Node>>,
+) -> error::Result<Vec<Node>> {
+    match (network.get(a), network.get(b)) {
+        (Some(start), Some(goal)) => algorithm(network, start, goal),
+        (None, _) => Err(Error::NotInNetwork(a.to_string())),
+        (_, None) => Err(Error::NotInNetwork(b.to_string())),
     }
 }
 
@@ -46,13 +52,7 @@ Retrieves a node from a network.
  */
 pub fn get(network: &Network<Node>, element: &str) -> Option<Node> {
     let hash = node!(element, 0, 0).hash;
-    for (i, elem) in network.hash_map.iter().enumerate() {
-        if elem.is_some() && i == hash as usize % consts::NETWORK_REM {
-            return network.hash_map[i];
-        }
-    }
-
-    None
+    network.hash_map.get(&hash).cloned()
 }
 
 /**
@@ -66,25 +66,29 @@ The shorest leg means that for every occurence of a path, the alternatives are s
 The path could not be found.
 
  */
-pub fn path_shortest_leg<'a>(
-    network: &'a Network<Node>,
+pub fn path_shortest_leg(
+    network: &Network<Node>,
     start: Node,
     goal: Node,
-) -> io::Result<Vec<Node>> {
-    // Create a new Branch-off path.
-    let format = |mut nodes: Vec<Node>, link: &HL, acc: u32| -> WNodes {
-        let node = network.hash_map[link.t as usize % consts::NETWORK_REM].unwrap();
-        let weight = acc + coordinate::distance(nodes.first().unwrap().geo, node.geo);
+) -> error::Result<Vec<Node>> {
+    let reverse = reverse_links(network);
+
+    // Paths are only expanded from the first time they reach a Node, which is also the cheapest.
+    let mut visited: HashSet<u64> = HashSet::new();
+    visited.insert(start.hash);
+
+    // Create a new Branch-off path, Links to nodes outside the network are skipped.
+    let format = |mut nodes: Vec<Node>, (t, link): (u64, &HL), acc: u32| -> Option<WNodes> {
+        let node = network.hash_map.get(&t)?.clone();
+        let weight = acc.saturating_add(link.cost(nodes.first().unwrap().geo(), node.geo()));
         nodes.insert(0, node);
-        WNodes { weight, nodes }
+        Some(WNodes { weight, nodes })
     };
 
     // Create the queue from connected links.
-    let mut queue: Vec<WNodes> = start
-        .links()
-        .iter()
-        .filter(|x| x.is_connected())
-        .map(|x| format(vec![start], x, 0))
+    let mut queue: Vec<WNodes> = neighbours(&start, &reverse)
+        .into_iter()
+        .filter_map(|x| format(vec![start.clone()], x, 0))
         .collect::<Vec<_>>();
 
     while !queue.is_empty() {
@@ -102,18 +106,221 @@ pub fn path_shortest_leg<'a>(
             return Ok(wnodes.nodes);
         }
 
+        // A cheaper path already went through this Node.
+        if !visited.insert(current.hash) {
+            continue;
+        }
+
         // Push new paths to the queue.
-        let _ = current
-            .links()
-            .iter()
-            .filter(|x| x.is_connected())
-            .map(|x| queue.push(format(wnodes.nodes.clone(), x, wnodes.weight)))
+        let _ = neighbours(current, &reverse)
+            .into_iter()
+            .filter(|(t, _)| !visited.contains(t))
+            .filter_map(|x| format(wnodes.nodes.clone(), x, wnodes.weight))
+            .map(|x| queue.push(x))
             .collect::<Vec<_>>();
     }
 
     // If we run out of items in the Queue, and we have not reacted
     // the goal, the path is invalid. And does not exist.
-    Err(Error::new(ErrorKind::Other, "not a valid path"))
+    Err(Error::NoPath)
+}
+
+/**
+Finds the cheapest path using Dijkstra's algorithm.
+
+The cost of a link is its weight, or the distance between the two Nodes it connects if unset.
+
+Derived from: https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm
+
+
+## Examples
+
+```
+# use pathtracer::{map::network, *};
+# fn main() -> Result<(), Error> {
+let nodes = Node::linked_list(Node::from_list(&[(0, 0), (10, 10), (20, 20)]));
+let network = Network::new(nodes)?;
+let path = network::path(&network, "C", "A", &network::dijkstra)?;
+assert_eq!(path.len(), 3);
+# Ok(())
+# }
+```
+
+
+## Errors
+
+The path could not be found.
+
+ */
+pub fn dijkstra(network: &Network<Node>, start: Node, goal: Node) -> error::Result<Vec<Node>> {
+    search(network, start, goal, &|_| 0)
+}
+
+/**
+Finds the cheapest path using A*, guided by the straight distance to the goal.
+
+Returns the same paths as dijkstra, but visits fewer Nodes on spread out networks.
+The great-circle distance is used if the goal was created with Node::from_lat_lon.
+
+The distance is scaled down by the cheapest link relative to the distance it spans,
+so link weights smaller than their distance still give the cheapest path.
+A link weighted 0 turns the search in to dijkstra.
+
+Derived from: https://en.wikipedia.org/wiki/A*_search_algorithm
+
+
+## Errors
+
+The path could not be found.
+
+ */
+pub fn astar(network: &Network<Node>, start: Node, goal: Node) -> error::Result<Vec<Node>> {
+    let geographic = goal.lat_lon().is_some();
+    let estimate = |a: Coordinate, b: Coordinate| {
+        if geographic {
+            // Rounded down, so it never exceeds the rounded link weights.
+            let a = coordinate::to_lat_lon(Point::from(a));
+            let b = coordinate::to_lat_lon(Point::from(b));
+            coordinate::haversine(a, b) as u32
+        } else {
+            coordinate::distance(a, b)
+        }
+    };
+    let scale = heuristic_scale(network, &estimate);
+    let geo = goal.geo();
+    search(network, start, goal, &|node| {
+        (f64::from(estimate(node.geo(), geo)) * scale) as u32
+    })
+}
+
+/**
+Returns the smallest ratio between the cost of a link and the estimate of the distance it spans, At most 1.
+
+Scaling the estimate by it never exceeds the cost of any path, which A* requires.
+ */
+fn heuristic_scale(
+    network: &Network<Node>,
+    estimate: &dyn Fn(Coordinate, Coordinate) -> u32,
+) -> f64 {
+    network
+        .hash_map
+        .values()
+        .flat_map(|node| node.links().iter().map(move |link| (node, link)))
+        .filter_map(|(node, link)| {
+            let to = network.hash_map.get(&link.t)?;
+            let spans = estimate(node.geo(), to.geo());
+            if spans == 0 {
+                return None;
+            }
+            Some(f64::from(link.cost(node.geo(), to.geo())) / f64::from(spans))
+        })
+        .fold(1.0, f64::min)
+}
+
+/**
+Implementation of dijkstra and astar, Use those for interfacing.
+
+The heuristic estimates the remaining cost from a Node to the goal.
+ */
+fn search(
+    network: &Network<Node>,
+    start: Node,
+    goal: Node,
+    heuristic: &dyn Fn(&Node) -> u32,
+) -> error::Result<Vec<Node>> {
+    let mut costs: HashMap<u64, u32> = HashMap::new();
+    let mut previous: HashMap<u64, u64> = HashMap::new();
+    let mut visited: HashSet<u64> = HashSet::new();
+    let mut queue = BinaryHeap::new();
+
+    costs.insert(start.hash, 0);
+    queue.push(cmp::Reverse((heuristic(&start), start.hash)));
+
+    let reverse = reverse_links(network);
+    while let Some(cmp::Reverse((_, hash))) = queue.pop() {
+        // A Node can be queued multiple times, only the cheapest is expanded.
+        if !visited.insert(hash) {
+            continue;
+        }
+
+        if hash == goal.hash {
+            return Ok(backtrack(network, &previous, goal));
+        }
+
+        let current = match network.hash_map.get(&hash) {
+            Some(node) => node,
+            None => continue,
+        };
+        let cost = costs[&hash];
+
+        for (t, link) in neighbours(current, &reverse) {
+            let next = match network.hash_map.get(&t) {
+                Some(node) if !visited.contains(&node.hash) => node,
+                _ => continue,
+            };
+
+            // Saturating, as costs across a wide map can exceed u32.
+            let next_cost = cost.saturating_add(link.cost(current.geo(), next.geo()));
+            let cheaper = match costs.get(&next.hash) {
+                Some(&c) => next_cost < c,
+                None => true,
+            };
+            if cheaper {
+                costs.insert(next.hash, next_cost);
+                previous.insert(next.hash, hash);
+                let estimate = next_cost.saturating_add(heuristic(next));
+                queue.push(cmp::Reverse((estimate, next.hash)));
+            }
+        }
+    }
+
+    Err(Error::NoPath)
+}
+
+/**
+Collects every undirected link in the network by the Node it points to.
+ */
+fn reverse_links(network: &Network<Node>) -> ReverseLinks<'_> {
+    let mut reverse: ReverseLinks = HashMap::new();
+    for node in network.hash_map.values() {
+        for link in node
+            .links()
+            .iter()
+            .filter(|x| x.is_connected() && x.undirected)
+        {
+            reverse.entry(link.t).or_default().push((link.f, link));
+        }
+    }
+    reverse
+}
+
+/**
+Returns the hashes of the Nodes reachable from node along with the link used to reach them.
+ */
+fn neighbours<'a>(node: &'a Node, reverse: &ReverseLinks<'a>) -> Vec<(u64, &'a HL)> {
+    let mut result = node
+        .links()
+        .iter()
+        .filter(|x| x.is_connected())
+        .map(|x| (x.t, x))
+        .collect::<Vec<_>>();
+    if let Some(links) = reverse.get(&node.hash) {
+        result.extend(links.iter().cloned());
+    }
+    result
+}
+
+/**
+Follows the previous steps from the goal back to the start.
+ */
+fn backtrack(network: &Network<Node>, previous: &HashMap<u64, u64>, goal: Node) -> Vec<Node> {
+    let mut hash = goal.hash;
+    let mut nodes = vec![goal];
+    while let Some(prev) = previous.get(&hash) {
+        hash = *prev;
+        nodes.push(network.hash_map[&hash].clone());
+    }
+    nodes
 }
 
 #[cfg(test)]
@@ -129,7 +336,7 @@ mod tests {
 
     // Helper
     fn network() -> Network<Node> {
-        Network::new(nodes())
+        Network::new(nodes()).unwrap()
     }
 
     #[test]
@@ -164,13 +371,197 @@ mod tests {
         let path = net.path("A", "D").unwrap();
         path_sl.reverse();
 
-        let f = |&p: &Node| p.geo;
+        let f = |p: &Node| p.geo();
         let v1 = path.iter().map(f).collect::<Vec<_>>();
         let v2 = path_sl.iter().map(f).collect::<Vec<_>>();
 
         assert_eq!(v1, v2);
     }
 
+    // Helper
+    fn grid(size: i32) -> Network<Node> {
+        let name = |x: i32, y: i32| format!("{},{}", x, y);
+        let mut nodes = Vec::new();
+        for y in 0..size {
+            for x in 0..size {
+                let mut node = node!(x * 10, y * 10);
+                for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)].iter() {
+                    let (nx, ny) = (x + dx, y + dy);
+                    if nx >= 0 && ny >= 0 && nx < size && ny < size {
+                        node.link(&node!(&name(nx * 10, ny * 10), nx * 10, ny * 10));
+                    }
+                }
+                nodes.push(node);
+            }
+        }
+        Network::new(nodes).unwrap()
+    }
+
+    #[test]
+    fn simple_networks_dijkstra_and_astar() {
+        let net = network();
+        let f = |p: &Node| p.geo();
+        let expected = path(&net, "D", "A", &path_shortest_leg).unwrap();
+        let expected = expected.iter().map(f).collect::<Vec<_>>();
+
+        for algorithm in [dijkstra, astar].iter() {
+            let path = path(&net, "D", "A", algorithm).unwrap();
+            assert_eq!(path.iter().map(f).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn path_names() {
+        let path = network().path_rev("D", "A").unwrap();
+        assert_eq!(node::path_string(&path), "A -> B -> C -> D");
+        assert_eq!(path[1].hl(0).unwrap().to_string(), "B -> A");
+    }
+
+    #[test]
+    fn start_is_goal() {
+        let net = network();
+        assert_eq!(path(&net, "B", "B", &dijkstra).unwrap().len(), 1);
+        assert_eq!(path(&net, "B", "B", &astar).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn cycles_without_route() {
+        let mut nodes = Node::from_list(&[(0, 0), (10, 0), (10, 10), (50, 50)]);
+        let (a, b, c) = (nodes[0].clone(), nodes[1].clone(), nodes[2].clone());
+        nodes[0].link(&b);
+        nodes[1].link(&c);
+        nodes[2].link(&a);
+        let net = Network::new(nodes).unwrap();
+
+        assert!(path(&net, "A", "D", &dijkstra).is_err());
+        assert!(path(&net, "A", "D", &astar).is_err());
+    }
+
+    #[test]
+    fn cheapest_route() {
+        // A -> E -> D is longer than A -> B -> C -> D.
+        let mut nodes = Node::from_list(&[(0, 0), (10, 1), (20, 1), (30, 0), (15, 400)]);
+        let copy = nodes.clone();
+        nodes[0].link(&copy[4]);
+        nodes[4].link(&copy[3]);
+        nodes[0].link(&copy[1]);
+        nodes[1].link(&copy[2]);
+        nodes[2].link(&copy[3]);
+        let net = Network::new(nodes).unwrap();
+
+        for algorithm in [dijkstra, astar].iter() {
+            let path = path(&net, "A", "D", algorithm).unwrap();
+            let hashes = path.iter().map(|n| n.hash).collect::<Vec<_>>();
+            let expected = [3, 2, 1, 0]
+                .iter()
+                .map(|&i| copy[i].hash)
+                .collect::<Vec<_>>();
+            assert_eq!(hashes, expected);
+        }
+    }
+
+    #[test]
+    fn costs_saturate() {
+        // The summed weights and the distance estimate exceed u32.
+        let mut nodes = Node::from_list(&[(-2_000_000_000, 0), (0, 0), (2_000_000_000, 0)]);
+        let copy = nodes.clone();
+        nodes[0].link_weighted(&copy[1], u32::MAX);
+        nodes[1].link_weighted(&copy[2], u32::MAX);
+        let net = Network::new(nodes).unwrap();
+        for algorithm in [dijkstra, astar].iter() {
+            assert_eq!(path(&net, "A", "C", algorithm).unwrap().len(), 3);
+        }
+    }
+
+    #[test]
+    fn weighted_route() {
+        // A -> C is the shortest by distance, but A -> B -> C is cheaper by weight.
+        let nodes = Node::from_list(&[(0, 0), (10, 100), (20, 0)]);
+        let link = |weights: Option<(u32, u32, u32)>| {
+            let (mut nodes, copy) = (nodes.clone(), nodes.clone());
+            nodes[0].link(&copy[1]);
+            nodes[1].link(&copy[2]);
+            nodes[0].link(&copy[2]);
+            if let Some((ab, bc, ac)) = weights {
+                nodes[0].links[0].weight(ab);
+                nodes[1].links[0].weight(bc);
+                nodes[0].links[1].weight(ac);
+            }
+            Network::new(nodes).unwrap()
+        };
+
+        let (geometric, weighted) = (link(None), link(Some((5, 5, 50))));
+        for algorithm in [dijkstra, astar, path_shortest_leg].iter() {
+            assert_eq!(path(&geometric, "A", "C", algorithm).unwrap().len(), 2);
+            assert_eq!(path(&weighted, "A", "C", algorithm).unwrap().len(), 3);
+        }
+    }
+
+    #[test]
+    fn astar_weights_below_distance() {
+        // The straight distance to C overestimates the cheap detour through B.
+        let mut nodes = Node::from_list(&[(0, 0), (500, 0), (1000, 0)]);
+        let copy = nodes.clone();
+        nodes[0].link_weighted(&copy[1], 10);
+        nodes[1].link_weighted(&copy[2], 10);
+        nodes[0].link_weighted(&copy[2], 30);
+        let net = Network::new(nodes).unwrap();
+        for algorithm in [dijkstra, astar].iter() {
+            assert_eq!(path(&net, "A", "C", algorithm).unwrap().len(), 3);
+        }
+
+        let net = Network::new(Node::linked_list(Node::from_list(&[(0, 0), (40, 30)]))).unwrap();
+        let estimate = |a, b| coordinate::distance(a, b);
+        assert_eq!(heuristic_scale(&net, &estimate), 1.0);
+    }
+
+    #[test]
+    fn unreachable_over_undirected_links() {
+        // A and B can travel back and forth, but never reach C.
+        let mut nodes = Node::from_list(&[(0, 0), (10, 10), (20, 20)]);
+        let copy = nodes.clone();
+        nodes[0].link_both(&copy[1]);
+        let net = Network::new(nodes).unwrap();
+
+        for algorithm in [dijkstra, astar, path_shortest_leg].iter() {
+            assert!(path(&net, "A", "C", algorithm).is_err());
+        }
+    }
+
+    #[test]
+    fn undirected_links() {
+        let mut nodes = Node::from_list(&[(0, 0), (10, 10), (20, 20)]);
+        let copy = nodes.clone();
+        nodes[0].link_both(&copy[1]);
+        nodes[2].link_both(&copy[1]);
+        let net = Network::new(nodes).unwrap();
+
+        for algorithm in [dijkstra, astar, path_shortest_leg].iter() {
+            assert_eq!(path(&net, "A", "C", algorithm).unwrap().len(), 3);
+            assert_eq!(path(&net, "C", "A", algorithm).unwrap().len(), 3);
+        }
+        assert_eq!(net.path("A", "C").unwrap(), net.path_rev("A", "C").unwrap());
+    }
+
+    #[test]
+    fn directed_links_stay_one_way() {
+        let mut nodes = Node::from_list(&[(0, 0), (10, 10)]);
+        let copy = nodes.clone();
+        nodes[0].link(&copy[1]);
+        let net = Network::new(nodes).unwrap();
+        assert!(path(&net, "A", "B", &dijkstra).is_ok());
+        assert!(path(&net, "B", "A", &dijkstra).is_err());
+    }
+
+    #[test]
+    fn large_grid() {
+        let net = grid(60);
+        let a = path(&net, "0,0", "590,590", &dijkstra).unwrap();
+        let b = path(&net, "0,0", "590,590", &astar).unwrap();
+        assert_eq!(a.len(), 119);
+        assert_eq!(node::path_distances(&a), node::path_distances(&b));
+    }
+
     #[test]
     fn valid_gets() {
         let network = network();
@@ -187,6 +578,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn large_network_keeps_every_node() {
+        let list = (0..2000).map(|i| (i, i)).collect::<Vec<_>>();
+        let nodes = coordinate::from_list(&list, &|c, i| Node::new(&i.to_string(), c));
+        let network = Network::new(Node::linked_list(nodes)).unwrap();
+        assert_eq!(network.len(), 2000);
+        assert!(get(&network, "0").is_some());
+        assert!(get(&network, "1999").is_some());
+        assert_eq!(network.path("0", "1999").unwrap().len(), 2000);
+    }
+
+    #[test]
+    fn path_through_hub() {
+        let mut hub = node!("hub", 0, 0);
+        let mut spokes = Node::from_list(&[(10, 0), (20, 0), (30, 0), (40, 0), (50, 0), (60, 0)]);
+        for spoke in spokes.iter_mut() {
+            spoke.link(&hub);
+        }
+        for spoke in spokes.iter() {
+            hub.link(spoke);
+        }
+        spokes.push(hub);
+
+        let network = Network::new(spokes).unwrap();
+        let path = network.path_rev("hub", "F").unwrap();
+        assert_eq!(path.len(), 2);
+    }
+
+    #[test]
+    fn duplicate_names() {
+        let mut nodes = nodes();
+        nodes.push(node!("A", 50, 50));
+        let err = Network::new(nodes).unwrap_err();
+        assert!(matches!(err, Error::Duplicate(_)));
+    }
+
+    #[test]
+    fn error_variants() {
+        let network = network();
+        let res = path(&network, "A", "E", &dijkstra);
+        assert!(matches!(res, Err(Error::NotInNetwork(ref name)) if name == "E"));
+        let res = path(&network, "A", "D", &dijkstra);
+        assert!(matches!(res, Err(Error::NoPath)));
+        let res = network.get("A").unwrap().hl(0).map(|_| ());
+        assert!(matches!(res, Err(Error::LinkIndex { index: 0, len: 0 })));
+    }
+
     #[test]
     fn invalid_network_1() {
         assert!(network().path("B", "E").is_err());
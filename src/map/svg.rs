@@ -0,0 +1,373 @@
+/*!
+  Svg wrapper for vector output.
+
+  Holds the elements drawn by the Map and writes them as a svg document.
+*/
+
+use super::{super::*, font};
+use image::Rgba;
+use std::{fmt, fs::File, io::prelude::*};
+
+/**
+A svg document of drawn elements.
+
+
+## See also
+
+Map::svg
+*/
+#[derive(Clone, Debug, Default)]
+pub struct Svg {
+    width: u32,
+    height: u32,
+    elements: Vec<String>,
+}
+
+impl Svg {
+    /**
+    Constructs an empty svg document with the given size.
+    */
+    pub fn new(width: u32, height: u32) -> Self {
+        Svg {
+            width,
+            height,
+            elements: Vec::new(),
+        }
+    }
+
+    /**
+    Returns a coordinate with the width and height of the document.
+    */
+    pub fn dimensions(&self) -> Coordinate {
+        coordinate!(self.width, self.height)
+    }
+
+    /**
+    Returns the drawn elements in the order they were drawn.
+    */
+    pub fn elements(&self) -> &[String] {
+        &self.elements
+    }
+
+    /**
+    Adds a filled circle.
+    */
+    pub fn circle(&mut self, center: Coordinate, radius: u32, color: Rgba<u8>) {
+        self.elements.push(format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {}/>",
+            center.x,
+            center.y,
+            radius,
+            fill(color)
+        ));
+    }
+
+    /**
+    Adds the outline of a circle, width wide inside the radius.
+    */
+    pub fn ring(&mut self, center: Coordinate, radius: u32, width: u32, color: Rgba<u8>) {
+        self.elements.push(format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" {}/>",
+            center.x,
+            center.y,
+            f64::from(radius) - f64::from(width) / 2.0,
+            stroke(color, width)
+        ));
+    }
+
+    /**
+    Adds a filled rectangle with the top-left corner at pos.
+    */
+    pub fn rect(&mut self, pos: Coordinate, width: u32, height: u32, color: Rgba<u8>) {
+        self.elements.push(format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {}/>",
+            pos.x,
+            pos.y,
+            width,
+            height,
+            fill(color)
+        ));
+    }
+
+    /**
+    Adds a filled polygon through the points.
+    */
+    pub fn polygon(&mut self, points: &[Coordinate], color: Rgba<u8>) {
+        self.elements.push(format!(
+            "<polygon points=\"{}\" {}/>",
+            points_attr(points),
+            fill(color)
+        ));
+    }
+
+    /**
+    Adds the outline of a polygon through the points.
+    */
+    pub fn outline(&mut self, points: &[Coordinate], color: Rgba<u8>, width: u32) {
+        self.elements.push(format!(
+            "<polygon points=\"{}\" fill=\"none\" {}/>",
+            points_attr(points),
+            stroke(color, width)
+        ));
+    }
+
+    /**
+    Adds a line through the points, A straight line if there are only two.
+    */
+    pub fn polyline(&mut self, points: &[Coordinate], color: Rgba<u8>, width: u32) {
+        let stroke = stroke(color, width);
+        let element = match points {
+            [a, b] => format!(
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {}/>",
+                a.x, a.y, b.x, b.y, stroke
+            ),
+            _ => format!(
+                "<polyline points=\"{}\" fill=\"none\" {}/>",
+                points_attr(points),
+                stroke
+            ),
+        };
+        self.elements.push(element);
+    }
+
+    /**
+    Adds text with its baseline starting at pos.
+
+    The font size is chosen so uppercase letters are as tall as the bitmap font in map::font.
+    */
+    pub fn text(&mut self, pos: Coordinate, text: &str, color: Rgba<u8>) {
+        self.elements.push(format!(
+            "<text x=\"{}\" y=\"{}\" font-family=\"monospace\" font-size=\"{}\" {}>{}</text>",
+            pos.x,
+            pos.y,
+            font::HEIGHT + 2,
+            fill(color),
+            escape(text)
+        ));
+    }
+
+    /**
+    Writes the document to disk at the given Path.
+
+
+    ## Errors
+
+    If the file can not be created or written to.
+    */
+    pub fn save(&self, path: &std::path::Path) -> error::Result<()> {
+        let mut file = File::create(path)?;
+        write!(file, "{}", self)?;
+        Ok(())
+    }
+}
+
+impl Canvas for Svg {
+    fn blank(width: u32, height: u32) -> Self {
+        Svg::new(width, height)
+    }
+
+    fn put(&mut self, pos: Coordinate, color: Rgba<u8>) {
+        self.rect(pos, 1, 1, color);
+    }
+
+    fn line(&mut self, points: &[Point], color: Rgba<u8>, width: u32) {
+        self.polyline(&tools::round(points), color, width);
+    }
+
+    fn fill(&mut self, pos: Point, shape: &Shape, size: u32, color: Rgba<u8>) {
+        let pos = Coordinate::from(pos);
+        let radius = Shape::radius(size as usize) as u32;
+        match shape {
+            Shape::Circle => self.circle(pos, radius, color),
+            Shape::Square => self.rect(pos, size, size, color),
+            Shape::Ring => self.ring(pos, radius, u32::from(consts::DEFAULT_RING_WIDTH), color),
+            Shape::Custom(points) => {
+                for c in points {
+                    self.put(pos + *c, color);
+                }
+            }
+            _ => {
+                let corners = shape.corners(size as usize).unwrap_or_default();
+                let points: Vec<Coordinate> = corners.into_iter().map(|c| pos + c).collect();
+                self.polygon(&points, color);
+            }
+        }
+    }
+
+    fn stroke(&mut self, pos: Point, shape: &Shape, size: u32, color: Rgba<u8>, width: u32) {
+        let pos = Coordinate::from(pos);
+        let radius = Shape::radius(size as usize) as u32;
+        let corners = match shape {
+            Shape::Circle => return self.ring(pos, radius, width, color),
+            Shape::Ring => {
+                // Both edges of the ring are outlined.
+                let ring = u32::from(consts::DEFAULT_RING_WIDTH);
+                self.ring(pos, radius, width, color);
+                return self.ring(pos, radius.saturating_sub(ring), width, color);
+            }
+            Shape::Square => vec![
+                coordinate!(),
+                coordinate!(size, 0),
+                coordinate!(size),
+                coordinate!(0, size),
+            ],
+            Shape::Custom(_) => {
+                for c in shape.outline(size as usize, width) {
+                    self.put(pos + c, color);
+                }
+                return;
+            }
+            _ => shape.corners(size as usize).unwrap_or_default(),
+        };
+        let points: Vec<Coordinate> = corners.into_iter().map(|c| pos + c).collect();
+        self.outline(&points, color, width);
+    }
+
+    fn polygon(&mut self, points: &[Point], color: Rgba<u8>) {
+        Svg::polygon(self, &tools::round(points), color);
+    }
+
+    fn text(&mut self, pos: Point, text: &str, color: Rgba<u8>) {
+        let pos = Coordinate::from(pos) + coordinate!(0, font::HEIGHT);
+        Svg::text(self, pos, text, color);
+    }
+}
+
+impl fmt::Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\">",
+            w = self.width,
+            h = self.height
+        )?;
+        for element in &self.elements {
+            writeln!(f, "  {}", element)?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+/**
+Formats the points as a svg points attribute value.
+*/
+fn points_attr(points: &[Coordinate]) -> String {
+    points
+        .iter()
+        .map(|c| format!("{},{}", c.x, c.y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/**
+Escapes the characters that have a meaning in svg text.
+*/
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/**
+Formats a color as a svg rgb value.
+*/
+fn rgb(color: Rgba<u8>) -> String {
+    format!("rgb({},{},{})", color[0], color[1], color[2])
+}
+
+/**
+Formats a color as svg fill attributes.
+*/
+fn fill(color: Rgba<u8>) -> String {
+    format!(
+        "fill=\"{}\" fill-opacity=\"{:.3}\"",
+        rgb(color),
+        f64::from(color[3]) / 255.0
+    )
+}
+
+/**
+Formats a color and width as svg stroke attributes.
+*/
+fn stroke(color: Rgba<u8>, width: u32) -> String {
+    format!(
+        "stroke=\"{}\" stroke-opacity=\"{:.3}\" stroke-width=\"{}\"",
+        rgb(color),
+        f64::from(color[3]) / 255.0,
+        width
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_document() {
+        let svg = Svg::new(20, 10).to_string();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains("viewBox=\"0 0 20 10\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn elements() {
+        let mut svg = Svg::new(50, 50);
+        let color = Rgba([255, 0, 10, 255]);
+        svg.circle(coordinate!(5, 5), 4, color);
+        svg.rect(coordinate!(1, 2), 3, 4, color);
+        svg.polyline(&[coordinate!(), coordinate!(10)], color, 2);
+        svg.polyline(
+            &[coordinate!(), coordinate!(10, 0), coordinate!(10)],
+            color,
+            2,
+        );
+
+        let e = svg.elements();
+        assert_eq!(
+            e[0],
+            "<circle cx=\"5\" cy=\"5\" r=\"4\" fill=\"rgb(255,0,10)\" fill-opacity=\"1.000\"/>"
+        );
+        assert!(e[1].starts_with("<rect x=\"1\" y=\"2\" width=\"3\" height=\"4\""));
+        assert!(e[2].starts_with("<line x1=\"0\" y1=\"0\" x2=\"10\" y2=\"10\""));
+        assert!(e[3].starts_with("<polyline points=\"0,0 10,0 10,10\""));
+    }
+
+    #[test]
+    fn circles_match_raster() {
+        let color = Rgba([0, 0, 0, 255]);
+        for &size in [2, 4, 9].iter() {
+            let mut svg = Svg::new(50, 50);
+            Canvas::fill(
+                &mut svg,
+                Point::new(20.0, 20.0),
+                &Shape::Circle,
+                size,
+                color,
+            );
+            Canvas::stroke(
+                &mut svg,
+                Point::new(20.0, 20.0),
+                &Shape::Circle,
+                size,
+                color,
+                0,
+            );
+
+            // The furthest position the raster circle covers from its centre.
+            let extent = Shape::Circle.area(size as usize).iter().map(|c| c.x).max();
+            let r = format!(" r=\"{}\" ", extent.unwrap());
+            assert!(svg.elements()[0].contains(&r), "{}", svg.elements()[0]);
+            assert!(svg.elements()[1].contains(&r), "{}", svg.elements()[1]);
+        }
+    }
+
+    #[test]
+    fn text_is_escaped() {
+        let mut svg = Svg::new(50, 50);
+        svg.text(coordinate!(1, 2), "a < b & c", Rgba([0, 0, 0, 255]));
+        assert!(svg.elements()[0].ends_with(">a &lt; b &amp; c</text>"));
+    }
+}
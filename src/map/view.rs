@@ -0,0 +1,442 @@
+/*!
+  Transform from drawn positions to canvas positions.
+
+  Used by the Map to scale and move the elements in to a canvas of a fixed size.
+*/
+
+use super::super::*;
+use image::Rgba;
+
+/**
+Scales a position and then moves it by origin.
+
+
+## See also
+
+Map::size
+*/
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct View {
+    pub scale_x: f64,
+    pub scale_y: f64,
+    pub origin: Coordinate,
+}
+
+impl View {
+    /**
+    Scales an area of the given size in to the space with its top-left corner at origin.
+
+    The scaled area is centred in the space, so it is cut off evenly when it does not fit.
+
+
+    ## Examples
+
+    ```
+    # use pathtracer::{map::view::View, *};
+    let area = Coordinate::new(100, 50);
+    let space = Coordinate::new(200, 200);
+
+    let view = View::fit(area, space, Coordinate::new(0, 0), ScaleMode::Fit);
+    assert_eq!(view.apply(Coordinate::new(0, 0)), Coordinate::new(0, 50));
+    assert_eq!(view.apply(Coordinate::new(100, 50)), Coordinate::new(200, 150));
+
+    let view = View::fit(area, space, Coordinate::new(0, 0), ScaleMode::Stretch);
+    assert_eq!(view.apply(Coordinate::new(100, 50)), Coordinate::new(200, 200));
+    ```
+    */
+    pub fn fit(area: Coordinate, space: Coordinate, origin: Coordinate, mode: ScaleMode) -> Self {
+        let ratio = |a: i32, s: i32| {
+            if a <= 0 {
+                1.0
+            } else {
+                f64::from(s.max(0)) / f64::from(a)
+            }
+        };
+        let (x, y) = (ratio(area.x, space.x), ratio(area.y, space.y));
+        let (scale_x, scale_y) = match mode {
+            ScaleMode::Fit => (x.min(y), x.min(y)),
+            ScaleMode::Fill => (x.max(y), x.max(y)),
+            ScaleMode::Stretch => (x, y),
+        };
+        let centre = |a: i32, s: i32, scale: f64| {
+            ((f64::from(s) - f64::from(a) * scale) / 2.0).round() as i32
+        };
+        View {
+            scale_x,
+            scale_y,
+            origin: origin
+                + Coordinate::new(
+                    centre(area.x, space.x, scale_x),
+                    centre(area.y, space.y, scale_y),
+                ),
+        }
+    }
+
+    /**
+    Returns the canvas position of a drawn position.
+    */
+    pub fn apply(&self, c: Coordinate) -> Coordinate {
+        let scale = |v: i32, s: f64| (f64::from(v) * s).round() as i32;
+        self.origin + Coordinate::new(scale(c.x, self.scale_x), scale(c.y, self.scale_y))
+    }
+}
+
+impl Default for View {
+    fn default() -> Self {
+        View {
+            scale_x: 1.0,
+            scale_y: 1.0,
+            origin: coordinate!(),
+        }
+    }
+}
+
+/**
+An affine transform from world positions to drawn positions, such as scaling, moving, rotating and flipping.
+
+Each step is applied after the steps before it.
+
+
+## Examples
+
+```
+# use pathtracer::{map::view::Transform, *};
+let transform = Transform::new().scale(2.0, 2.0).translate(10.0, 0.0);
+assert_eq!(transform.apply(Point::new(1.0, 1.5)), Point::new(12.0, 3.0));
+
+let transform = Transform::new().flip_y().rotate(90.0);
+assert_eq!(transform.place(Coordinate::new(10, 0)), Coordinate::new(0, 10));
+```
+*/
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform {
+    // x' = a * x + c * y + e, y' = b * x + d * y + f
+    matrix: [f64; 6],
+}
+
+impl Transform {
+    /**
+    Constructs a transform that leaves positions unchanged.
+    */
+    pub fn new() -> Self {
+        Transform {
+            matrix: [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+        }
+    }
+
+    /**
+    Applies next after this transform.
+    */
+    pub fn then(self, next: Transform) -> Self {
+        let [a, b, c, d, e, f] = self.matrix;
+        let [na, nb, nc, nd, ne, nf] = next.matrix;
+        Transform {
+            matrix: [
+                na * a + nc * b,
+                nb * a + nd * b,
+                na * c + nc * d,
+                nb * c + nd * d,
+                na * e + nc * f + ne,
+                nb * e + nd * f + nf,
+            ],
+        }
+    }
+
+    /**
+    Scales positions away from 0 0.
+    */
+    pub fn scale(self, x: f64, y: f64) -> Self {
+        self.then(Transform {
+            matrix: [x, 0.0, 0.0, y, 0.0, 0.0],
+        })
+    }
+
+    /**
+    Moves positions by x and y.
+    */
+    pub fn translate(self, x: f64, y: f64) -> Self {
+        self.then(Transform {
+            matrix: [1.0, 0.0, 0.0, 1.0, x, y],
+        })
+    }
+
+    /**
+    Rotates positions around 0 0 by deg degrees, Clockwise on the canvas since y grows downwards.
+    */
+    pub fn rotate(self, deg: f64) -> Self {
+        let (sin, cos) = deg.to_radians().sin_cos();
+        self.then(Transform {
+            matrix: [cos, sin, -sin, cos, 0.0, 0.0],
+        })
+    }
+
+    /**
+    Mirrors positions vertically, so y grows upwards as in most world coordinate systems.
+    */
+    pub fn flip_y(self) -> Self {
+        self.scale(1.0, -1.0)
+    }
+
+    /**
+    Returns the transformed position.
+    */
+    pub fn apply(&self, p: Point) -> Point {
+        let [a, b, c, d, e, f] = self.matrix;
+        Point::new(a * p.x + c * p.y + e, b * p.x + d * p.y + f)
+    }
+
+    /**
+    Returns the transformed position, rounded to the nearest whole position.
+    */
+    pub fn place(&self, c: Coordinate) -> Coordinate {
+        Coordinate::from(self.apply(Point::from(c)))
+    }
+
+    /**
+    Returns the smallest area containing the transformed corners of the area between min and max.
+    */
+    pub fn bounds(&self, min: Coordinate, max: Coordinate) -> (Coordinate, Coordinate) {
+        let corners = [
+            min,
+            coordinate!(max.x, min.y),
+            max,
+            coordinate!(min.x, max.y),
+        ];
+        let points: Vec<Point> = corners
+            .iter()
+            .map(|c| self.apply(Point::from(*c)))
+            .collect();
+        let low = points
+            .iter()
+            .fold(Point::new(f64::MAX, f64::MAX), |acc, p| {
+                Point::new(acc.x.min(p.x), acc.y.min(p.y))
+            });
+        let high = points
+            .iter()
+            .fold(Point::new(f64::MIN, f64::MIN), |acc, p| {
+                Point::new(acc.x.max(p.x), acc.y.max(p.y))
+            });
+        (
+            coordinate!(low.x.floor(), low.y.floor()),
+            coordinate!(high.x.ceil(), high.y.ceil()),
+        )
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::new()
+    }
+}
+
+/**
+A Canvas that places everything drawn through it, before drawing it on to the inner canvas.
+
+Drawn positions are moved by origin to get their world position, which is projected, transformed and then placed with the View.
+Positions are only rounded once placed.
+Line widths, shape sizes and text are not scaled or rotated.
+*/
+#[derive(Clone, Debug)]
+pub struct Viewed<C: Canvas> {
+    pub canvas: C,
+    pub origin: Coordinate,
+    pub projection: Projection,
+    pub transform: Transform,
+    pub view: View,
+}
+
+impl<C: Canvas> Viewed<C> {
+    /**
+    Returns the canvas position of a drawn position.
+    */
+    fn place(&self, p: Point) -> Point {
+        let world = p + Point::from(self.origin);
+        let p = self.transform.apply(self.projection.project(world));
+        Point::new(
+            f64::from(self.view.origin.x) + p.x * self.view.scale_x,
+            f64::from(self.view.origin.y) + p.y * self.view.scale_y,
+        )
+    }
+
+    /**
+    Returns the position a shape is drawn at, keeping the centre of the shape in place.
+
+    Only positions are placed, shapes keep their size.
+    */
+    fn shape(&self, pos: Point, shape: &Shape, size: u32) -> Point {
+        match shape {
+            Shape::Circle | Shape::Ring => self.place(pos),
+            _ => {
+                let half = Point::from(coordinate!(size / 2));
+                self.place(pos + half) - half
+            }
+        }
+    }
+}
+
+impl<C: Canvas> Canvas for Viewed<C> {
+    fn blank(width: u32, height: u32) -> Self {
+        Viewed {
+            canvas: C::blank(width, height),
+            origin: coordinate!(),
+            projection: Projection::default(),
+            transform: Transform::new(),
+            view: View::default(),
+        }
+    }
+
+    fn put(&mut self, pos: Coordinate, color: Rgba<u8>) {
+        let pos = Coordinate::from(self.place(Point::from(pos)));
+        self.canvas.put(pos, color);
+    }
+
+    fn blend(&mut self, pos: Coordinate, color: Rgba<u8>, coverage: f64) {
+        let pos = Coordinate::from(self.place(Point::from(pos)));
+        self.canvas.blend(pos, color, coverage);
+    }
+
+    fn anti_aliased(&self) -> bool {
+        self.canvas.anti_aliased()
+    }
+
+    fn set_anti_aliased(&mut self, enabled: bool) {
+        self.canvas.set_anti_aliased(enabled);
+    }
+
+    fn set_blend_mode(&mut self, mode: BlendMode) {
+        self.canvas.set_blend_mode(mode);
+    }
+
+    fn line(&mut self, points: &[Point], color: Rgba<u8>, width: u32) {
+        let points: Vec<Point> = points.iter().map(|p| self.place(*p)).collect();
+        self.canvas.line(&points, color, width);
+    }
+
+    fn fill(&mut self, pos: Point, shape: &Shape, size: u32, color: Rgba<u8>) {
+        let pos = self.shape(pos, shape, size);
+        self.canvas.fill(pos, shape, size, color);
+    }
+
+    fn stroke(&mut self, pos: Point, shape: &Shape, size: u32, color: Rgba<u8>, width: u32) {
+        let pos = self.shape(pos, shape, size);
+        self.canvas.stroke(pos, shape, size, color, width);
+    }
+
+    fn polygon(&mut self, points: &[Point], color: Rgba<u8>) {
+        let points: Vec<Point> = points.iter().map(|p| self.place(*p)).collect();
+        self.canvas.polygon(&points, color);
+    }
+
+    fn text(&mut self, pos: Point, text: &str, color: Rgba<u8>) {
+        let pos = self.place(pos);
+        self.canvas.text(pos, text, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewed(transform: Transform, view: View) -> Viewed<map::svg::Svg> {
+        Viewed {
+            canvas: map::svg::Svg::new(0, 0),
+            origin: coordinate!(),
+            projection: Projection::default(),
+            transform,
+            view,
+        }
+    }
+
+    #[test]
+    fn default_is_identity() {
+        let viewed = viewed(Transform::default(), View::default());
+        let p = Point::new(-3.0, 7.5);
+        assert_eq!(viewed.place(p), p);
+        assert_eq!(
+            viewed.shape(Point::new(4.0, 4.0), &Shape::Square, 9),
+            Point::new(4.0, 4.0)
+        );
+    }
+
+    #[test]
+    fn fill_covers_the_space() {
+        let view = View::fit(
+            coordinate!(100, 50),
+            coordinate!(200, 200),
+            coordinate!(10, 10),
+            ScaleMode::Fill,
+        );
+        assert_eq!(view.scale_x, 4.0);
+        assert_eq!(view.apply(coordinate!(0, 0)), coordinate!(-90, 10));
+        assert_eq!(view.apply(coordinate!(100, 50)), coordinate!(310, 210));
+    }
+
+    #[test]
+    fn shapes_keep_their_centre() {
+        let view = View {
+            scale_x: 2.0,
+            scale_y: 2.0,
+            origin: coordinate!(),
+        };
+        let viewed = viewed(Transform::new(), view);
+        assert_eq!(
+            viewed.shape(Point::new(10.0, 10.0), &Shape::Circle, 4),
+            Point::new(20.0, 20.0)
+        );
+        assert_eq!(
+            viewed.shape(Point::new(10.0, 10.0), &Shape::Square, 4),
+            Point::new(22.0, 22.0)
+        );
+    }
+
+    #[test]
+    fn transform_steps_in_order() {
+        let p = Point::new(1.0, 0.0);
+        let moved_first = Transform::new().translate(1.0, 0.0).scale(3.0, 3.0);
+        let scaled_first = Transform::new().scale(3.0, 3.0).translate(1.0, 0.0);
+        assert_eq!(moved_first.apply(p), Point::new(6.0, 0.0));
+        assert_eq!(scaled_first.apply(p), Point::new(4.0, 0.0));
+
+        let turned = Transform::new().rotate(90.0).apply(p);
+        assert!(turned.x.abs() < 1e-9 && (turned.y - 1.0).abs() < 1e-9);
+        assert_eq!(Transform::new().flip_y().apply(p), p);
+        assert_eq!(
+            Transform::new().flip_y().apply(Point::new(0.0, 2.5)),
+            Point::new(0.0, -2.5)
+        );
+    }
+
+    #[test]
+    fn transform_bounds() {
+        let transform = Transform::new().rotate(45.0);
+        let (min, max) = transform.bounds(coordinate!(0, 0), coordinate!(10, 10));
+        assert_eq!(min, coordinate!(-8, 0));
+        assert_eq!(max, coordinate!(8, 15));
+        let (min, max) = Transform::new()
+            .flip_y()
+            .bounds(coordinate!(0, 0), coordinate!(10, 10));
+        assert_eq!((min, max), (coordinate!(0, -10), coordinate!(10, 0)));
+    }
+
+    #[test]
+    fn sub_pixel_part_is_placed() {
+        let view = View {
+            scale_x: 10.0,
+            scale_y: 10.0,
+            origin: coordinate!(),
+        };
+        let viewed = viewed(Transform::new(), view);
+        let placed = viewed.place(Point::new(1.25, 0.7));
+        assert_eq!(Coordinate::from(placed), coordinate!(13, 7));
+        assert_eq!(viewed.place(Point::new(1.0, 1.0)), Point::new(10.0, 10.0));
+    }
+
+    #[test]
+    fn sub_pixel_positions_are_scaled_before_rounding() {
+        let transform = Transform::new().scale(10.0, 10.0);
+        assert_eq!(
+            Coordinate::from(transform.apply(Point::new(1.25, 0.04))),
+            coordinate!(13, 0)
+        );
+    }
+}
@@ -9,7 +9,7 @@ use std::{
     io::{self, prelude::*},
 };
 
-impl<'a> PartialEq for Node {
+impl PartialEq for Node {
     fn eq(&self, other: &Node) -> bool {
         self.hash == other.hash
     }
@@ -24,7 +24,7 @@ Returns a list of Strings split using \n in a Vec.
 Could not open file.
 Could not read content to string.
 */
-pub fn get_node_names(path: &str) -> Result<Vec<String>, io::Error> {
+pub fn get_node_names(path: &str) -> error::Result<Vec<String>> {
     let mut file = File::open(path)?;
     let mut contents = String::new();
     file.read_to_string(&mut contents)?;
@@ -37,58 +37,282 @@ pub fn get_node_names(path: &str) -> Result<Vec<String>, io::Error> {
 /**
 Write the positions to a file.
 
+Names are written as a third column so they are kept when read with from_file.
+Names matching the default "x,y" name are left out, and names containing a comma,
+a quote or surrounding whitespace are written within quotes, as "a, b".
+Unnamed Nodes are written with an empty quoted name, so they keep their hash.
+
 
 ## Errors
 
 Could not open file.
 Could not write to file.
+Error::Io with InvalidInput if a name contains a line break, which the format can not hold.
  */
-pub fn write_file(path: &str, nodes: &[Node]) -> std::io::Result<()> {
+pub fn write_file(path: &str, nodes: &[Node]) -> error::Result<()> {
+    if let Some(name) = nodes
+        .iter()
+        .filter_map(Node::name)
+        .find(|x| x.contains(&['\n', '\r'][..]))
+    {
+        let message = format!("name {:?} contains a line break", name);
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, message).into());
+    }
+
     let mut file = File::create(path)?;
     for n in nodes.iter() {
         let p = n.position();
-        writeln!(&mut file, "{},{}", p.x, p.y)?;
+        match n.name() {
+            Some(name) if name == format!("{},{}", p.x, p.y) => {
+                writeln!(&mut file, "{},{}", p.x, p.y)?
+            }
+            name => writeln!(&mut file, "{},{},{}", p.x, p.y, quote(name.unwrap_or("")))?,
+        }
     }
     Ok(())
 }
 
 /**
-Reads the positions from a file.
-
+Quotes a column if it would not be read back as written, Quotes within are doubled.
+*/
+fn quote(column: &str) -> String {
+    if column.is_empty() || column.contains(&[',', '"'][..]) || column.trim() != column {
+        format!("\"{}\"", column.replace('"', "\"\""))
+    } else {
+        column.to_string()
+    }
+}
 
-Expects eact line format to be:
+/**
+Reads the positions from a file.
 
-x,y
+See parse for the expected format.
 
 
 ## Errors
 
 Could not open file.
 Could not read to string.
+Error::Parse if a line could not be parsed.
 
 */
-pub fn from_file(path: &str) -> Result<Vec<Node>, io::Error> {
+pub fn from_file(path: &str) -> error::Result<Vec<Node>> {
     let mut contents = String::new();
     let mut file = File::open(path)?;
     file.read_to_string(&mut contents)?;
-    Ok(contents.split('\n').fold(vec![], |mut acc, x| {
-        let vals = x.split(',').collect::<Vec<_>>();
-        let c = Coordinate::new(
-            vals[0].parse::<i16>().unwrap(),
-            vals[1].parse::<i16>().unwrap(),
-        );
-        acc.push(Node::new(x, c));
-        acc
-    }))
+    parse(&contents)
+}
+
+/**
+Parses Nodes from csv content.
+
+
+Expects each line format to be:
+
+x,y,name,color,radius
+
+Only x and y are required, empty columns use the default value.
+The name defaults to "x,y", the color is hex formatted as #rrggbb or #rrggbbaa.
+Columns within quotes are kept as written, including commas, and "" is read as a quote.
+
+Blank lines and lines starting with # are skipped, as is a header row.
+
+
+## Examples
+
+```
+# use pathtracer::*;
+# fn main() -> Result<(), Error> {
+let content = "x,y,name\n# A comment.\n100, 20, Home\n\n40,60,,#ff0000,8\n";
+let nodes = node::parse(content)?;
+assert_eq!(nodes.len(), 2);
+assert_eq!(nodes[0], Node::new("Home", Coordinate::new(100, 20)));
+assert_eq!(nodes[1].radius, Some(8));
+# Ok(())
+# }
+```
+
+
+## Errors
+
+Error::Parse with the line number of the first line that could not be parsed.
+
+*/
+pub fn parse(content: &str) -> error::Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match parse_line(line) {
+            Ok(node) => nodes.push(node),
+            Err(_) if nodes.is_empty() && is_header(line) => (),
+            Err(message) => {
+                return Err(Error::Parse {
+                    line: i + 1,
+                    message,
+                })
+            }
+        }
+    }
+    Ok(nodes)
+}
+
+/**
+Returns true if the line starts with a column name instead of a value.
+*/
+fn is_header(line: &str) -> bool {
+    matches!(line.chars().next(), Some(c) if c.is_alphabetic())
+}
+
+/**
+Parses a single trimmed line of csv content in to a Node.
+*/
+fn parse_line(line: &str) -> Result<Node, String> {
+    let columns = split_columns(line)?;
+    if columns.len() < 2 || columns.len() > 5 {
+        return Err(format!("expected 2 to 5 columns, found {}", columns.len()));
+    }
+
+    let number = |name: &str, value: &str| {
+        value
+            .parse::<i32>()
+            .map_err(|e| format!("invalid {} '{}': {}", name, value, e))
+    };
+    // Quoted columns are kept even if empty.
+    let optional = |i: usize| {
+        columns
+            .get(i)
+            .filter(|(x, quoted)| *quoted || !x.is_empty())
+            .map(|(x, _)| x.as_str())
+    };
+
+    let geo = Coordinate::new(number("x", &columns[0].0)?, number("y", &columns[1].0)?);
+    let name = optional(2).map_or_else(|| format!("{},{}", geo.x, geo.y), |x| x.to_string());
+    let mut node = Node::new(&name, geo);
+
+    if let Some(color) = optional(3) {
+        node.color = parse_color(color).ok_or(format!("invalid color '{}'", color))?;
+    }
+
+    if let Some(radius) = optional(4) {
+        let radius = radius
+            .parse::<u32>()
+            .map_err(|e| format!("invalid radius '{}': {}", radius, e))?;
+        node.radius = Some(radius);
+    }
+
+    Ok(node)
+}
+
+/**
+Splits a line in to columns, and whether each was quoted.
+
+Unquoted columns are trimmed, Quoted columns are kept as written.
+*/
+fn split_columns(line: &str) -> Result<Vec<(String, bool)>, String> {
+    let mut columns = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars
+            .peek()
+            .map_or(false, |c| *c != ',' && c.is_whitespace())
+        {
+            chars.next();
+        }
+
+        let mut column = String::new();
+        let quoted = chars.peek() == Some(&'"');
+        if quoted {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        column.push('"');
+                    }
+                    Some('"') => break,
+                    Some(c) => column.push(c),
+                    None => return Err("unterminated quote".to_string()),
+                }
+            }
+            while chars.peek().map_or(false, |c| c.is_whitespace()) {
+                chars.next();
+            }
+            if let Some(c) = chars.peek().filter(|c| **c != ',') {
+                return Err(format!("unexpected '{}' after quote", c));
+            }
+        } else {
+            while let Some(&c) = chars.peek().filter(|c| **c != ',') {
+                column.push(c);
+                chars.next();
+            }
+            column = column.trim_end().to_string();
+        }
+        columns.push((column, quoted));
+
+        // Either a comma is next, or the line has ended.
+        if chars.next().is_none() {
+            return Ok(columns);
+        }
+    }
+}
+
+/**
+Parses a hex color formatted as #rrggbb or #rrggbbaa.
+*/
+fn parse_color(hex: &str) -> Option<image::Rgba<u8>> {
+    let hex = hex.trim_start_matches('#');
+    if !(hex.len() == 6 || hex.len() == 8) || !hex.is_ascii() {
+        return None;
+    }
+
+    let mut rgba = [u8::MAX; 4];
+    for (i, channel) in rgba.iter_mut().enumerate().take(hex.len() / 2) {
+        *channel = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(image::Rgba(rgba))
 }
 
 /**
 Prints the distance between all the nodes paths and returns a summary of the total distance.
 */
 pub fn path_print(path: &[Node]) -> u32 {
+    debug!("{}", path_string(path));
     verbose_path(path, true)
 }
 
+/**
+Returns the path as a string of names, such as "A -> B -> C".
+
+Nodes without a name are shown by their position.
+
+
+## Examples
+
+```
+# use pathtracer::*;
+let nodes = Node::from_list(&[(0, 0), (10, 10), (20, 20)]);
+assert_eq!(node::path_string(&nodes), "A -> B -> C");
+```
+*/
+pub fn path_string(path: &[Node]) -> String {
+    path.iter()
+        .map(display_name)
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+/**
+Returns the name of the node, or the position if it has no name.
+*/
+fn display_name(node: &Node) -> String {
+    node.name()
+        .map_or_else(|| format!("({})", node.position()), str::to_string)
+}
+
 /**
  Returns the sum distance that all the nodes' are from each other.
 */
@@ -99,13 +323,13 @@ pub fn path_distances(path: &[Node]) -> u32 {
 /**
 Implementation of path_distance and path_print, Use those for interfacing.
 */
-fn verbose_path<L: Location>(path: &[L], stdout: bool) -> u32 {
+fn verbose_path(path: &[Node], stdout: bool) -> u32 {
     let mut prev = Coordinate::new(0, 0);
     let distance = path.iter().fold(0, |sum, x| {
         let dis = coordinate::distance(prev, x.position());
         prev = x.position();
         if stdout {
-            debug!("{} - distance: {}", x.position(), dis);
+            debug!("{} {} - distance: {}", display_name(x), x.position(), dis);
         }
         sum + dis
     });
@@ -125,4 +349,135 @@ mod tests {
         let res = get_node_names("invalid path");
         assert!(res.is_err());
     }
+
+    #[test]
+    fn test_parse_positions() {
+        let nodes = parse("100,20\n40,60\n30,30\n").unwrap();
+        let geos = nodes.iter().map(|n| n.geo()).collect::<Vec<_>>();
+        assert_eq!(
+            geos,
+            Coordinate::from_list(&[(100, 20), (40, 60), (30, 30)])
+        );
+        assert_eq!(nodes[0], node!(100, 20));
+    }
+
+    #[test]
+    fn test_parse_whitespace_and_comments() {
+        let content = "\r\n# comment\n  1 , 2  \r\n\n   \n#3,4\n5,6";
+        let nodes = parse(content).unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].geo(), Coordinate::new(1, 2));
+        assert_eq!(nodes[1].geo(), Coordinate::new(5, 6));
+    }
+
+    #[test]
+    fn test_parse_optional_columns() {
+        let nodes = parse("x,y,name,color,radius\n1,2,A,#0a0b0c,5\n3,4,,#01020304\n").unwrap();
+        assert_eq!(nodes[0], node!("A", 1, 2));
+        assert_eq!(nodes[0].color, image::Rgba([10, 11, 12, 255]));
+        assert_eq!(nodes[0].radius, Some(5));
+        assert_eq!(nodes[1], node!(3, 4));
+        assert_eq!(nodes[1].color, image::Rgba([1, 2, 3, 4]));
+        assert_eq!(nodes[1].radius, None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let line = |content| match parse(content) {
+            Err(Error::Parse { line, .. }) => line,
+            _ => 0,
+        };
+        assert_eq!(line("1,2\n3"), 2);
+        assert_eq!(line("1,2\n\n3,y"), 3);
+        assert_eq!(line("1,2\nx,y"), 2);
+        assert_eq!(line("1,2,a,#zz0000"), 1);
+        assert_eq!(line("1,2,a,,-1"), 1);
+        assert_eq!(line("9999999999,2"), 1);
+    }
+
+    #[test]
+    fn test_from_file() {
+        let path = "/tmp/test_node_from_file.csv";
+        let nodes = vec![node!(1, 2), node!(-3, 4)];
+        write_file(path, &nodes).unwrap();
+        assert_eq!(from_file(path).unwrap(), nodes);
+    }
+
+    #[test]
+    fn test_from_file_names() {
+        let path = "/tmp/test_node_from_file_names.csv";
+        let nodes = Node::from_list(&[(1, 2), (-3, 4)]);
+        write_file(path, &nodes).unwrap();
+        let read = from_file(path).unwrap();
+        assert_eq!(read, nodes);
+        assert_eq!(read[1].name(), Some("B"));
+    }
+
+    #[test]
+    fn test_from_file_quoted_names() {
+        let path = "/tmp/test_node_from_file_quoted.csv";
+        let nodes = vec![
+            Node::new("Stockholm, Sweden", Coordinate::new(1, 2)),
+            Node::new("The \"Hub\"", Coordinate::new(3, 4)),
+            Node::new(" padded ", Coordinate::new(5, 6)),
+            Node::new("", Coordinate::new(7, 8)),
+            node!(9, 10),
+        ];
+        let mut linked = nodes.clone();
+        linked[0].link(&nodes[1]);
+        write_file(path, &linked).unwrap();
+
+        let read = from_file(path).unwrap();
+        let names = read.iter().map(Node::name).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                Some("Stockholm, Sweden"),
+                Some("The \"Hub\""),
+                Some(" padded "),
+                None,
+                Some("9,10")
+            ]
+        );
+        let hashes = |list: &[Node]| list.iter().map(|n| n.hash).collect::<Vec<_>>();
+        assert_eq!(hashes(&read), hashes(&nodes));
+        assert_eq!(linked[0].links()[0].t, read[1].hash);
+    }
+
+    #[test]
+    fn test_write_file_line_break() {
+        let path = "/tmp/test_node_write_line_break.csv";
+        let nodes = vec![Node::new("two\nlines", Coordinate::new(0, 0))];
+        match write_file(path, &nodes) {
+            Err(Error::Io(e)) => assert_eq!(e.kind(), io::ErrorKind::InvalidInput),
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_split_columns() {
+        let columns = |line| {
+            split_columns(line)
+                .unwrap()
+                .into_iter()
+                .map(|(x, _)| x)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(columns("1, 2 ,a b"), vec!["1", "2", "a b"]);
+        assert_eq!(columns("1,2,"), vec!["1", "2", ""]);
+        assert_eq!(
+            columns("1,2, \"a, \"\"b\"\"\" ,#ff0000"),
+            vec!["1", "2", "a, \"b\"", "#ff0000"]
+        );
+        assert!(split_columns("1,2,\"open").is_err());
+        assert!(split_columns("1,2,\"a\"b").is_err());
+    }
+
+    #[test]
+    fn test_path_string() {
+        let mut nodes = Node::from_list(&[(0, 0), (10, 10)]);
+        nodes.push(Node::new("", Coordinate::new(5, 6)));
+        assert_eq!(path_string(&nodes), "A -> B -> (5, 6)");
+        assert_eq!(path_string(&[]), "");
+    }
 }
@@ -8,7 +8,7 @@ fn city() {
 
     for y in 0..city_size / 2 {
         for x in 0..city_size * 2 {
-            let mut node = node!(spread * x as i16, spread * y as i16);
+            let mut node = node!(spread * x, spread * y);
             node.color = tools::seed_rgba((city_size * x + spread * y) as u64);
             pos.push(node);
         }
@@ -34,7 +34,7 @@ fn mvp() -> std::io::Result<()> {
 
     let nodes = Node::from_list(&pos);
     let nodes = Node::linked_list(nodes);
-    let net = Network::new(nodes);
+    let net = Network::new(nodes)?;
     let path = net.path("A", "E");
     let rev_path = net.path_rev("E", "A");
 
@@ -68,16 +68,17 @@ fn ellipse() {
     group.add(50);
     group.radius(800);
     group.nodes = Node::linked_list(group.nodes);
-    group.each(&|node: &mut Node| match node.hl_mut(0) {
-        Ok(e) => e.style(EdgeStyle::Ellipse),
-        Err(_) => (),
+    group.each(&|node: &mut Node| {
+        if let Ok(e) = node.hl_mut(0) {
+            e.style(EdgeStyle::Ellipse)
+        }
     });
     Map::new().map(&[group]);
 }
 
 #[test]
 fn cycles() -> std::io::Result<()> {
-    let mut gif = map::gif::Gif::new("/tmp/out.gif", 50, 50);
+    let mut gif = map::gif::Gif::new("/tmp/out.gif", 50, 50)?;
     let balls = Node::from_list(&[(20, 20), (30, 30), (40, 40)]);
 
     gif.cycle(2, balls);
@@ -96,9 +97,9 @@ fn graph() {
 
     let mut pos = Vec::new();
     let y = vec![5, 30, 45, 35, 40, 80, 75, 70, 25, 30];
-    let spread = 300 / (y.len() - 1) as i16;
+    let spread = 300 / (y.len() - 1) as i32;
     for (i, y) in y.iter().enumerate() {
-        pos.push((i as i16 * spread, -*y));
+        pos.push((i as i32 * spread, -*y));
     }
     let line = Node::linked_list(Node::from_list(&pos));
 
@@ -32,3 +32,14 @@ fn group_conversion() {
     let group = Group::from(node);
     assert_eq!(group.position(), Coordinate::new(4, 3));
 }
+
+#[test]
+fn far_conversion() {
+    let c = Coordinate::new(i32::MAX, i32::MAX);
+    assert_eq!(Node::from(c).position(), c);
+    assert_eq!(Group::from(c).position(), c);
+
+    let group = Group::new_simple(i32::MAX, 1);
+    assert_eq!(group.position(), Coordinate::new(i32::MAX, 1));
+    assert_eq!(group.hash(), Group::new("2147483648", c).hash());
+}
@@ -26,7 +26,7 @@ mod integration {
             g
         }
 
-        fn no_move(a: &Vec<Node>, b: &Vec<Node>) {
+        fn no_move(a: &[Node], b: &[Node]) {
             let matching = a
                 .iter()
                 .zip(b.iter())
@@ -105,7 +105,7 @@ mod integration {
         }
 
         #[test]
-        fn max_links() {
+        fn many_links() {
             let mut a = Node::new("A", Coordinate::new(0, 0));
             let nodes = Node::linked_list(Node::from_list(&[
                 (0, 0),
@@ -122,15 +122,32 @@ mod integration {
 
             for (i, node) in nodes.iter().enumerate() {
                 a.link(node);
-                if i < 5 {
-                    assert!(
-                        a.hl(i).is_ok(),
-                        format!("expected {}, got {}", i, a.get_link_avail_index())
-                    );
-                } else {
-                    assert!(a.hl(i).is_err(), "Exceeding max_link should return Err");
-                }
+                assert!(
+                    a.hl(i).is_ok(),
+                    "expected {}, got {}",
+                    i,
+                    a.get_link_avail_index()
+                );
+            }
+            assert!(a.hl(nodes.len()).is_err());
+            assert_eq!(a.links().len(), nodes.len());
+        }
+
+        #[test]
+        fn hub_links() {
+            let mut hub = Node::new("hub", Coordinate::new(0, 0));
+            let spokes = (0..500)
+                .map(|i| Node::new(&i.to_string(), Coordinate::new(i, 0)))
+                .collect::<Vec<_>>();
+
+            for spoke in spokes.iter() {
+                hub.link(spoke);
             }
+
+            assert_eq!(hub.get_link_avail_index(), 500);
+            assert!(spokes.iter().all(|s| hub.is_directly_connected(s)));
+            hub.hl_mut(499).unwrap().style(EdgeStyle::Straight);
+            assert_eq!(hub.hl(499).unwrap().style, EdgeStyle::Straight);
         }
 
         #[test]
@@ -203,4 +220,823 @@ mod integration {
             assert!(b.settings.is_directly_connected(&a));
         }
     }
+
+    mod canvas {
+        use crate::*;
+
+        /**
+        In-memory canvas recording every put.
+        */
+        struct Record(Vec<(Coordinate, image::Rgba<u8>)>);
+
+        impl Canvas for Record {
+            fn blank(_: u32, _: u32) -> Self {
+                Record(Vec::new())
+            }
+
+            fn put(&mut self, pos: Coordinate, color: image::Rgba<u8>) {
+                self.0.push((pos, color));
+            }
+        }
+
+        fn nodes() -> Vec<Node> {
+            let mut nodes = Node::linked_list(Node::from_list(&[(0, 0), (40, 20), (10, 60)]));
+            nodes[1].hl_mut(0).unwrap().style(EdgeStyle::Ellipse);
+            nodes
+        }
+
+        fn shapes() -> Vec<Shape> {
+            vec![
+                Shape::Square,
+                Shape::Circle,
+                Shape::Triangle,
+                Shape::Hexagon,
+                Shape::Diamond,
+                Shape::Ring,
+                Shape::Star,
+                Shape::Custom(Coordinate::from_list(&[(0, 0), (1, 1), (2, 0)])),
+            ]
+        }
+
+        #[test]
+        fn record_matches_image() {
+            for shape in &shapes() {
+                let record = Map::<Record>::empty().map_shape(&nodes(), shape).consume();
+                let image = Map::new().map_shape(&nodes(), shape).consume();
+                assert!(!record.0.is_empty());
+                // Only the last color put at a position is visible.
+                let mut seen = std::collections::HashSet::new();
+                for (pos, color) in record.0.iter().rev() {
+                    if seen.insert((pos.x, pos.y)) {
+                        assert_eq!(image.image().get_pixel(pos.x as u32, pos.y as u32), color);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn anti_aliased_edges_are_blended() {
+            let mut nodes = nodes();
+            nodes.push(node!(-20, -20));
+            nodes.push(node!(80, 80));
+            let partial = |image: &IW| {
+                image
+                    .image()
+                    .pixels()
+                    .filter(|p| p.data[3] > 0 && p.data[3] < u8::MAX)
+                    .count()
+            };
+            for shape in &shapes() {
+                let smooth = Map::new()
+                    .anti_alias(true)
+                    .map_shape(&nodes, shape)
+                    .consume();
+                let sharp = Map::new().map_shape(&nodes, shape).consume();
+                assert!(smooth.anti_aliased() && !sharp.anti_aliased());
+                assert!(partial(&smooth) > 0);
+                assert_eq!(partial(&sharp), 0);
+            }
+        }
+
+        #[test]
+        fn blend_outside_is_skipped() {
+            let mut image = IW::blank(2, 2);
+            image.blend(&coordinate!(-1, 0), image::Rgba([255; 4]), 1.0);
+            image.blend(&coordinate!(2, 2), image::Rgba([255; 4]), 1.0);
+            assert!(image.image().pixels().all(|p| p.data == [0; 4]));
+        }
+
+        #[test]
+        fn group_offsets() {
+            let mut group = Group::new_simple(100, 100);
+            group.push(node!(110, 110));
+            let record = Record::blank(0, 0);
+            let record = group.draw(record, Point::default(), &Shape::Square);
+            assert!(record.0.iter().all(|(pos, _)| pos.x >= 100));
+        }
+    }
+
+    mod labels {
+        use crate::*;
+
+        fn labelled(placement: Placement) -> Node {
+            let mut node = node!("A", 0, 0);
+            node.set_label("Label", placement);
+            node
+        }
+
+        #[test]
+        fn placement() {
+            let above = labelled(Placement::Above).min_max();
+            let below = labelled(Placement::Below).min_max();
+            let center = labelled(Placement::Center).min_max();
+            let none = node!("A", 0, 0).min_max();
+            assert!(above.0.y < none.0.y && above.1.y == none.1.y);
+            assert!(below.1.y > none.1.y && below.0.y == none.0.y);
+            assert!(center.0.x < none.0.x && center.0.y == none.0.y);
+        }
+
+        #[test]
+        fn map_fits_labels() {
+            for placement in &[Placement::Above, Placement::Below, Placement::Center] {
+                let nodes = vec![labelled(*placement), node!(20, 20)];
+                let with = Map::new().map(&nodes).consume();
+                let without = Map::new().labels(false).map(&nodes).consume();
+                assert_ne!(with.image().to_vec(), without.image().to_vec());
+            }
+        }
+
+        #[test]
+        fn group_label() {
+            let mut group = Group::new_simple(0, 0);
+            group.push(node!(10, 10));
+            let (_, max) = group.min_max();
+            group.set_label("Group", Placement::Below);
+            assert_eq!(group.label(), Some("Group"));
+            assert!(group.min_max().1.y > max.y);
+
+            let svg = Map::svg().map(&[group]).consume();
+            let text = svg.elements().last().unwrap();
+            assert!(text.starts_with("<text") && text.ends_with(">Group</text>"));
+        }
+    }
+
+    mod edges {
+        use crate::*;
+
+        fn linked(style: EdgeStyle) -> Vec<Node> {
+            let b = node!("B", 0, 0);
+            let mut a = node!("A", 40, 20);
+            a.link(&b);
+            a.hl_mut(0).unwrap().style(style);
+            // Unlinked corners leave room for the ellipse, which curves outside the endpoints.
+            vec![a, b, node!(-40, -40), node!(80, 80)]
+        }
+
+        #[test]
+        fn waypoints_end_at_to() {
+            let styles = [
+                EdgeStyle::Direct,
+                EdgeStyle::Straight,
+                EdgeStyle::Ellipse,
+                EdgeStyle::Bezier,
+                EdgeStyle::Orthogonal,
+            ];
+            for style in &styles {
+                let nodes = linked(*style);
+                let link = nodes[0].hl(0).unwrap();
+                let points = link.waypoints(nodes[0].geo(), nodes[1].geo());
+                assert_eq!(points.first(), Some(&nodes[0].geo()));
+                assert_eq!(points.last(), Some(&nodes[1].geo()));
+            }
+        }
+
+        #[test]
+        fn arrowhead_points_at_to() {
+            let mut link = HL::new(1, 2);
+            let points = [Point::new(0.0, 0.0), Point::new(20.0, 0.0)];
+            assert_eq!(link.arrowhead(&points, 4), None);
+
+            link.arrow(6);
+            let head = tools::round(&link.arrowhead(&points, 4).unwrap());
+            assert_eq!(
+                head,
+                [coordinate!(16, 0), coordinate!(10, 3), coordinate!(10, -3)]
+            );
+            let head = tools::round(&link.arrowhead(&points, 10).unwrap());
+            assert_eq!(
+                head,
+                [coordinate!(10, 0), coordinate!(4, 3), coordinate!(4, -3)]
+            );
+
+            link.undirected = true;
+            assert_eq!(link.arrowhead(&points, 4), None);
+        }
+
+        #[test]
+        fn arrowhead_stops_at_target_size() {
+            let mut b = node!(40, 0);
+            b.radius = Some(12);
+            let mut a = node!(0, 0);
+            a.link(&b);
+            assert_eq!(a.hl(0).unwrap().to_size, None);
+            a.hl_mut(0).unwrap().to_size(b.size());
+            a.hl_mut(0).unwrap().arrow(6);
+            let link = a.hl(0).unwrap();
+            assert_eq!(link.to_size, Some(12));
+            let (min, max) = link.arrow_area().unwrap();
+            assert_eq!(max.x, 28);
+            assert!(min.x >= 22);
+
+            let (mut c, d) = (
+                Group::new("C", coordinate!()),
+                Group::new("D", coordinate!(40, 0)),
+            );
+            c.link(&d);
+            assert_eq!(c.settings.hl(0).unwrap().to_size, Some(d.size()));
+        }
+
+        #[test]
+        fn arrows_and_labels_drawn_for_all_styles() {
+            let styles = [
+                EdgeStyle::Direct,
+                EdgeStyle::Straight,
+                EdgeStyle::Ellipse,
+                EdgeStyle::Bezier,
+                EdgeStyle::Orthogonal,
+            ];
+            for style in &styles {
+                let plain = linked(*style);
+                let mut nodes = plain.clone();
+                nodes[0].hl_mut(0).unwrap().arrow(12);
+                nodes[0].hl_mut(0).unwrap().label("45");
+
+                let (min, max) = nodes[0].min_max();
+                let (plain_min, plain_max) = plain[0].min_max();
+                assert!(min.x <= plain_min.x && max.x >= plain_max.x);
+
+                let svg = Map::svg().map(&nodes).consume();
+                let elements = svg.elements();
+                assert!(elements.iter().any(|x| x.starts_with("<polygon")));
+                assert!(elements.last().unwrap().ends_with(">45</text>"));
+
+                let with = Map::new().map(&nodes).consume();
+                let without = Map::new().map(&plain).consume();
+                assert_ne!(with.image().to_vec(), without.image().to_vec());
+            }
+        }
+
+        #[test]
+        fn color_and_width() {
+            assert_eq!(HL::default().color, consts::DEFAULT_LINK_RGBA);
+            assert_eq!(HL::default().width, u32::from(consts::DEFAULT_LINK_SIZE));
+
+            let red = image::Rgba([255, 0, 0, 128]);
+            let mut nodes = linked(EdgeStyle::Direct);
+            nodes[0].hl_mut(0).unwrap().color(red);
+            nodes[0].hl_mut(0).unwrap().width(5);
+
+            let svg = Map::svg().map(&nodes).consume();
+            assert!(svg.elements()[0]
+                .contains("stroke=\"rgb(255,0,0)\" stroke-opacity=\"0.502\" stroke-width=\"5\""));
+
+            let map = Map::new().map(&nodes);
+            let offset = map::gen_bounds(&nodes).1;
+            let image = map.consume();
+            let mid = tools::midpoint(nodes[0].geo(), nodes[1].geo()) + offset;
+            assert_eq!(*image.image().get_pixel(mid.x as u32, mid.y as u32), red);
+        }
+    }
+
+    mod blend {
+        use crate::*;
+
+        fn overlapping(alpha: u8) -> Vec<Node> {
+            let mut a = node!(0, 0);
+            let mut b = node!(2, 0);
+            a.color = image::Rgba([255, 0, 0, alpha]);
+            b.color = image::Rgba([0, 0, 255, alpha]);
+            vec![a, b]
+        }
+
+        fn center(mode: BlendMode, alpha: u8) -> image::Rgba<u8> {
+            let nodes = overlapping(alpha);
+            let map = Map::new().blend_mode(mode).map(&nodes);
+            let offset = map::gen_bounds(&nodes).1;
+            let image = map.consume();
+            assert_eq!(image.blend_mode(), mode);
+            *image
+                .image()
+                .get_pixel(offset.x as u32 + 2, offset.y as u32 + 1)
+        }
+
+        #[test]
+        fn opaque_colors_replace() {
+            let blue = image::Rgba([0, 0, 255, 255]);
+            assert_eq!(center(BlendMode::Replace, 255), blue);
+            assert_eq!(center(BlendMode::Over, 255), blue);
+        }
+
+        #[test]
+        fn transparent_colors_show_through() {
+            let over = center(BlendMode::Over, 128);
+            assert!(over.data[0] > 0 && over.data[2] > 0);
+            assert!(over.data[3] > 128);
+            assert_eq!(
+                center(BlendMode::Replace, 128),
+                image::Rgba([0, 0, 255, 128])
+            );
+        }
+
+        #[test]
+        fn add_and_multiply() {
+            let add = center(BlendMode::Add, 255);
+            assert_eq!(add, image::Rgba([255, 0, 255, 255]));
+            let multiply = center(BlendMode::Multiply, 255);
+            assert_eq!(multiply, image::Rgba([0, 0, 0, 255]));
+        }
+
+        #[test]
+        fn mix_on_transparent() {
+            let red = image::Rgba([255, 0, 0, 100]);
+            let empty = image::Rgba([0; 4]);
+            for mode in &[BlendMode::Over, BlendMode::Add, BlendMode::Multiply] {
+                assert_eq!(mode.mix(red, empty, 1.0), red);
+            }
+            assert_eq!(BlendMode::Over.mix(red, empty, 0.0), empty);
+        }
+    }
+
+    mod shapes {
+        use crate::*;
+
+        #[test]
+        fn polygons_fit_their_size() {
+            for shape in &[Shape::Triangle, Shape::Hexagon, Shape::Diamond, Shape::Star] {
+                let area = shape.area(9);
+                assert!(!area.is_empty());
+                assert!(area.iter().all(|c| c.x >= 0 && c.x <= 8));
+                assert!(area.iter().all(|c| c.y >= 0 && c.y <= 9));
+            }
+        }
+
+        #[test]
+        fn ring_is_hollow() {
+            let ring = Shape::Ring.area(8);
+            let circle = Shape::Circle.area(8);
+            assert!(ring.contains(&coordinate!(7, 0)));
+            assert!(!ring.contains(&coordinate!(0, 0)));
+            assert!(circle.contains(&coordinate!(0, 0)));
+        }
+
+        #[test]
+        fn star_is_smaller_than_diamond_or_hexagon() {
+            let count = |shape: Shape| tools::unique(shape.area(21)).len();
+            assert!(count(Shape::Star) < count(Shape::Diamond));
+            assert!(count(Shape::Diamond) < count(Shape::Hexagon));
+        }
+
+        #[test]
+        fn custom_from_image() {
+            let mut image = image::RgbaImage::new(4, 4);
+            image.put_pixel(0, 0, image::Rgba([255, 0, 0, 255]));
+            image.put_pixel(3, 1, image::Rgba([0, 0, 0, 1]));
+            let shape = Shape::from_image(&image);
+            assert_eq!(shape.area(0), Coordinate::from_list(&[(0, 0), (3, 1)]));
+
+            let map = Map::new()
+                .map_shape(&[node!(0, 0), node!(20, 20)], &shape)
+                .consume();
+            let drawn = map.image().pixels().filter(|p| p.data[3] > 0).count();
+            assert_eq!(drawn, 4);
+        }
+
+        #[test]
+        fn node_shape_overrides_map() {
+            let mut nodes = Node::from_list(&[(0, 0), (20, 20), (40, 0)]);
+            nodes[1].shape = Some(Shape::Circle);
+            let svg = Map::svg().map_shape(&nodes, &Shape::Diamond).consume();
+            let tags: Vec<&str> = svg.elements().iter().map(|x| &x[..7]).collect();
+            assert_eq!(tags, ["<polygo", "<circle", "<polygo"]);
+        }
+
+        #[test]
+        fn group_shape_is_default_for_nodes() {
+            let mut group = Group::new_simple(0, 0);
+            group.shape(Shape::Ring);
+            group.push(node!(0, 0));
+            let mut square = node!(20, 20);
+            square.shape = Some(Shape::Square);
+            group.push(square);
+
+            let svg = Map::svg().map_shape(&[group], &Shape::Star).consume();
+            assert!(svg.elements()[0].contains("fill=\"none\""));
+            assert!(svg.elements()[1].starts_with("<rect"));
+        }
+
+        fn styled(shape: Shape) -> Vec<Node> {
+            let mut node = node!(0, 0);
+            node.radius = Some(10);
+            node.shape = Some(shape);
+            node.color = image::Rgba([0, 0, 255, 255]);
+            node.stroke = Some(image::Rgba([255, 0, 0, 255]));
+            node.stroke_width = 2;
+            vec![node, node!(40, 40)]
+        }
+
+        #[test]
+        fn stroke_and_fill_every_shape() {
+            let shapes = [
+                Shape::Square,
+                Shape::Circle,
+                Shape::Triangle,
+                Shape::Hexagon,
+                Shape::Diamond,
+                Shape::Ring,
+                Shape::Star,
+            ];
+            for shape in &shapes {
+                for anti_alias in &[false, true] {
+                    let image = Map::new()
+                        .anti_alias(*anti_alias)
+                        .map(&styled(shape.clone()))
+                        .consume();
+                    let count =
+                        |color: [u8; 4]| image.image().pixels().filter(|p| p.data == color).count();
+                    assert!(count([255, 0, 0, 255]) > 0, "{:?}", shape);
+                    if *shape != Shape::Ring {
+                        assert!(count([0, 0, 255, 255]) > 0, "{:?}", shape);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn hollow_nodes_are_outlined() {
+            let mut nodes = styled(Shape::Square);
+            nodes[0].hollow = true;
+            nodes[0].stroke = None;
+            let image = Map::new().map(&nodes).consume();
+            let pixels: Vec<_> = image.image().pixels().map(|p| p.data).collect();
+            assert!(pixels.contains(&[0, 0, 255, 255]));
+
+            let center = nodes[0].geo() + coordinate!(5, 5) + map::gen_bounds(&nodes).1;
+            let pixel = image.image().get_pixel(center.x as u32, center.y as u32);
+            assert_eq!(pixel.data, [0; 4]);
+
+            let svg = Map::svg().map(&nodes).consume();
+            assert!(svg.elements()[0].starts_with("<polygon"));
+            assert!(svg.elements()[0].contains("fill=\"none\""));
+        }
+
+        #[test]
+        fn svg_outputs() {
+            let nodes = [node!(0, 0), node!(20, 20)];
+            let tags = [
+                (Shape::Hexagon, "<polygon"),
+                (Shape::Diamond, "<polygon"),
+                (Shape::Star, "<polygon"),
+                (Shape::Ring, "<circle"),
+                (Shape::Custom(vec![coordinate!()]), "<rect"),
+            ];
+            for (shape, tag) in &tags {
+                let svg = Map::svg().map_shape(&nodes, shape).consume();
+                assert!(svg.elements().iter().all(|x| x.starts_with(tag)));
+            }
+            let svg = Map::svg().map_shape(&nodes, &Shape::Ring).consume();
+            assert!(svg.elements()[0].contains("fill=\"none\""));
+        }
+    }
+
+    mod viewport {
+        use crate::*;
+
+        fn drawn(image: &IW) -> Vec<(u32, u32)> {
+            image
+                .image()
+                .enumerate_pixels()
+                .filter(|(_, _, p)| p.data[3] > 0)
+                .map(|(x, y, _)| (x, y))
+                .collect()
+        }
+
+        fn nodes() -> Vec<Node> {
+            Node::linked_list(Node::from_list(&[(0, 0), (100, 20), (40, 50)]))
+        }
+
+        #[test]
+        fn fixed_size_for_every_mode() {
+            for mode in &[ScaleMode::Fit, ScaleMode::Fill, ScaleMode::Stretch] {
+                let image = Map::with_size(300, 100)
+                    .scale_mode(*mode)
+                    .map(&nodes())
+                    .consume();
+                assert_eq!(image.dimensions(), coordinate!(300, 100));
+                let pixels = drawn(&image);
+                let right = pixels.iter().map(|p| p.0).max().unwrap();
+                match mode {
+                    ScaleMode::Fit => assert!(right < 250),
+                    _ => assert!(right > 280),
+                }
+            }
+        }
+
+        #[test]
+        fn margin_is_left_empty() {
+            for map in &[Map::new().margin(10), Map::with_size(200, 200).margin(10)] {
+                let image = map.clone().labels(false).map(&nodes()).consume();
+                let size = image.dimensions();
+                let pixels = drawn(&image);
+                assert!(!pixels.is_empty());
+                assert!(pixels.iter().all(|(x, y)| *x >= 10
+                    && *y >= 10
+                    && *x < size.x as u32 - 10
+                    && *y < size.y as u32 - 10));
+            }
+        }
+
+        #[test]
+        fn layers_share_the_viewport() {
+            let inside = [node!(50, 50)];
+            let outside = [node!(500, 500), node!(-500, 0)];
+            let image = Map::with_size(200, 200)
+                .viewport(coordinate!(0, 0), coordinate!(100, 100))
+                .map(&inside)
+                .map(&outside)
+                .consume();
+            assert_eq!(image.image().get_pixel(103, 103).data[3], 255);
+            assert!(drawn(&image).iter().all(|(x, y)| *x < 110 && *y < 110));
+
+            let svg = Map::svg()
+                .size(200, 200)
+                .viewport(coordinate!(0, 0), coordinate!(100, 100))
+                .map_shape(&inside, &Shape::Circle)
+                .consume();
+            assert!(svg.elements()[0].starts_with("<circle cx=\"100\" cy=\"100\""));
+        }
+
+        #[test]
+        fn transform_is_applied_when_drawn() {
+            let nodes = [node!("A", 0, 0), node!("B", 0, 40)];
+            let cy = |svg: &map::svg::Svg, i: usize| -> i32 {
+                let element = &svg.elements()[i];
+                let start = element.find("cy=\"").unwrap() + 4;
+                let end = start + element[start..].find('"').unwrap();
+                element[start..end].parse().unwrap()
+            };
+
+            let plain = Map::svg().map_shape(&nodes, &Shape::Circle).consume();
+            assert!(cy(&plain, 0) < cy(&plain, 1));
+
+            let flipped = Map::svg()
+                .transform(map::view::Transform::new().flip_y())
+                .map_shape(&nodes, &Shape::Circle)
+                .consume();
+            assert!(cy(&flipped, 0) > cy(&flipped, 1));
+            assert_eq!(flipped.dimensions(), plain.dimensions());
+
+            // Nodes keep their world position, only the drawing is changed.
+            assert_eq!(nodes[1].geo(), coordinate!(0, 40));
+        }
+
+        #[test]
+        fn sub_pixel_world_positions() {
+            let mut node = node!();
+            node.set_world(Point::new(2.3, 2.3));
+            let mut group = Group::new_simple(0, 0);
+            group.push(node);
+            for _ in 0..1000 {
+                group.rotate(3.6);
+            }
+            assert_eq!(group.nodes()[0].geo(), coordinate!(2, 2));
+            let world = group.nodes()[0].world();
+            assert!((world.x - 2.3).abs() < 1e-6 && (world.y - 2.3).abs() < 1e-6);
+        }
+
+        #[test]
+        fn sub_pixel_positions_are_drawn() {
+            let right = |at: f64| {
+                let mut node = node!("B", 0, 0);
+                node.set_world(Point::new(at, at));
+                let image = Map::new()
+                    .labels(false)
+                    .transform(map::view::Transform::new().scale(20.0, 20.0))
+                    .map(&[node!("A", 0, 0), node])
+                    .consume();
+                let right = drawn(&image).iter().map(|p| p.0).max().unwrap();
+                (image.dimensions(), right)
+            };
+            let (whole, part) = (right(3.0), right(3.4));
+            assert_eq!(whole.0, part.0);
+            assert_eq!(part.1, whole.1 + 8);
+        }
+
+        #[test]
+        fn absolute_maps_keep_the_settings() {
+            let nodes = Node::from_list(&[(0, 0), (40, 40)]);
+            let svg = Map::svg()
+                .margin(5)
+                .transform(map::view::Transform::new().scale(2.0, 2.0))
+                .map_absolute(&nodes)
+                .consume();
+            // The margin is kept and the transform applied, but the area is not moved.
+            assert!(svg.elements()[0].starts_with("<rect x=\"7\" y=\"7\""));
+            assert!(svg.elements()[1].starts_with("<rect x=\"87\" y=\"87\""));
+            assert_eq!(svg.dimensions(), Coordinate::new(106, 106));
+        }
+
+        #[test]
+        #[should_panic(expected = "Map::size must be set before the first elements are mapped")]
+        fn settings_after_mapping_panic() {
+            let nodes = Node::from_list(&[(0, 0), (40, 40)]);
+            Map::new().map(&nodes).size(100, 100);
+        }
+
+        #[test]
+        fn links_meet_scaled_nodes() {
+            let number = |element: &str, name: &str| -> i32 {
+                let start = element.find(&format!("{}=\"", name)).unwrap() + name.len() + 2;
+                let end = start + element[start..].find('"').unwrap();
+                element[start..end].parse().unwrap()
+            };
+            let mut b = node!("B", 0, 0);
+            b.set_world(Point::new(3.4, 1.3));
+            let mut a = node!("A", 0, 0);
+            a.link(&b);
+
+            let svg = Map::svg()
+                .labels(false)
+                .transform(map::view::Transform::new().scale(20.0, 20.0))
+                .map_shape(&[a, b], &Shape::Circle)
+                .consume();
+            let line = svg
+                .elements()
+                .iter()
+                .find(|x| x.starts_with("<line"))
+                .unwrap();
+            let circle = svg
+                .elements()
+                .iter()
+                .rev()
+                .find(|x| x.starts_with("<circle"))
+                .unwrap();
+            assert_eq!(number(line, "x2"), number(circle, "cx"));
+            assert_eq!(number(line, "y2"), number(circle, "cy"));
+        }
+    }
+
+    mod geo {
+        use crate::*;
+
+        fn equator() -> Vec<Node> {
+            // A -> D -> C goes north, A -> B -> C stays on the equator.
+            let mut nodes = vec![
+                Node::from_lat_lon("A", 0.0, 0.0),
+                Node::from_lat_lon("B", 0.0, 10.0),
+                Node::from_lat_lon("C", 0.0, 20.0),
+                Node::from_lat_lon("D", 30.0, 10.0),
+            ];
+            let copy = nodes.clone();
+            nodes[0].link(&copy[3]);
+            nodes[3].link(&copy[2]);
+            nodes[0].link(&copy[1]);
+            nodes[1].link(&copy[2]);
+            nodes
+        }
+
+        fn north() -> Vec<Node> {
+            // A -> B -> C follows the 60th parallel, which is the straight line when drawn.
+            // A -> D -> C follows the great circle, which is shorter on the ground.
+            let mut nodes = vec![
+                Node::from_lat_lon("A", 60.0, 0.0),
+                Node::from_lat_lon("B", 60.0, 45.0),
+                Node::from_lat_lon("C", 60.0, 90.0),
+                Node::from_lat_lon("D", 67.8, 45.0),
+            ];
+            let copy = nodes.clone();
+            nodes[0].link(&copy[1]);
+            nodes[1].link(&copy[2]);
+            nodes[0].link(&copy[3]);
+            nodes[3].link(&copy[2]);
+            nodes
+        }
+
+        #[test]
+        fn lat_lon_round_trips() {
+            let node = Node::from_lat_lon("Oslo", 59.9139, 10.7522);
+            let (lat, lon) = node.lat_lon().unwrap();
+            assert!((lat - 59.9139).abs() < 1e-6);
+            assert!((lon - 10.7522).abs() < 1e-6);
+            assert!(Node::new("A", coordinate!()).lat_lon().is_none());
+        }
+
+        #[test]
+        fn links_are_weighted_in_metres() {
+            let nodes = equator();
+            let weight = nodes[0].hl(1).unwrap().weight.unwrap();
+            // Ten degrees along the equator is roughly 1112 km.
+            assert!((1_111_000..1_113_000).contains(&weight));
+        }
+
+        #[test]
+        fn paths_follow_the_ground() {
+            let nodes = north();
+            let drawn = |a: usize, b: usize| coordinate::distance(nodes[a].geo(), nodes[b].geo());
+            assert!(drawn(0, 1) + drawn(1, 2) < drawn(0, 3) + drawn(3, 2));
+
+            let network = Network::new(nodes).unwrap();
+            for algorithm in [map::network::dijkstra, map::network::astar].iter() {
+                let path = map::network::path(&network, "A", "C", algorithm).unwrap();
+                let names = path.iter().map(|n| n.name().unwrap()).collect::<Vec<_>>();
+                assert_eq!(names, vec!["C", "D", "A"]);
+            }
+        }
+
+        #[test]
+        fn projections_keep_order() {
+            let nodes = equator();
+            for projection in [Projection::Equirectangular, Projection::WebMercator].iter() {
+                let image = Map::with_size(300, 300)
+                    .projection(*projection)
+                    .map(&nodes)
+                    .consume();
+                assert_eq!(image.dimensions(), Coordinate::new(300, 300));
+
+                let image = Map::new().projection(*projection).map(&nodes).consume();
+                let size = image.dimensions();
+                assert_eq!(size.x.max(size.y), 1000);
+            }
+
+            // Mercator stretches latitude, so the northern node is further from the equator.
+            let (a, d) = (Point::from(nodes[0].geo()), Point::from(nodes[3].geo()));
+            let mercator = Projection::WebMercator;
+            assert!(mercator.project(d).y - mercator.project(a).y < d.y - a.y);
+            assert!(mercator.project(a).y.abs() < 1e-6);
+        }
+
+        #[test]
+        fn lat_lon_without_projection() {
+            let nodes = equator();
+            let plain = Map::new().map(&nodes).consume();
+            let size = plain.dimensions();
+            assert_eq!(size.x.max(size.y), 1000);
+
+            let flat = Map::new()
+                .projection(Projection::Equirectangular)
+                .map(&nodes)
+                .consume();
+            assert_eq!(flat.image().to_vec(), plain.image().to_vec());
+        }
+    }
+
+    mod layout {
+        use crate::{layout::ForceDirected, *};
+
+        fn closest(groups: &[Group]) -> u32 {
+            groups
+                .iter()
+                .enumerate()
+                .flat_map(|(i, a)| {
+                    groups[i + 1..]
+                        .iter()
+                        .map(move |b| coordinate::distance(a.position(), b.position()))
+                })
+                .min()
+                .unwrap()
+        }
+
+        #[test]
+        fn converted_groups_spread_out() {
+            let content = "alpha\nbeta\ngamma\ndelta\nalpha\nepsilon\nbeta\nzeta\neta";
+            let groups = data::convert(content, &|_| true);
+            let before = closest(&groups);
+
+            let groups = layout::force_directed(groups, &ForceDirected::default().seed(1));
+            assert!(closest(&groups) > before);
+
+            let image = Map::new().map(&groups).consume();
+            let (width, height) = image.image().dimensions();
+            assert!(width >= 500 && height >= 500);
+        }
+
+        #[test]
+        fn dependency_graph() {
+            // Crates and what they depend on, Written as a build tool would list them.
+            let names = ["app", "cli", "core", "io", "log", "util"];
+            let depends = [
+                (0, 1),
+                (0, 2),
+                (1, 2),
+                (1, 4),
+                (2, 3),
+                (2, 5),
+                (3, 5),
+                (4, 5),
+            ];
+            let mut nodes = names
+                .iter()
+                .map(|name| Node::new(name, coordinate!()))
+                .collect::<Vec<_>>();
+            let copy = nodes.clone();
+            for &(from, to) in depends.iter() {
+                nodes[from].link(&copy[to]);
+                nodes[from]
+                    .links
+                    .last_mut()
+                    .unwrap()
+                    .style(EdgeStyle::Straight);
+            }
+
+            let nodes = layout::hierarchical(nodes, &layout::Hierarchical::default());
+            for node in &nodes {
+                for link in &node.links {
+                    assert!(link.to.unwrap().y > link.from.unwrap().y);
+                }
+            }
+            assert_eq!(nodes[5].y(), 4 * 60);
+
+            let image = Map::new().map(&nodes).consume();
+            assert!(image.image().dimensions().1 > 4 * 60);
+        }
+    }
 }
@@ -5,12 +5,13 @@ Utility functions
 extern crate image;
 extern crate rand;
 
-use super::{Coordinate, Hash};
+use super::{Coordinate, Hash, Point};
 use image::Rgba;
 use rand::{distributions::Uniform, Rng};
 
 use std::{
     cmp::{max, min},
+    collections::{HashMap, HashSet},
     f64,
     mem::swap,
 };
@@ -56,7 +57,7 @@ assert_eq!(
 ```
 */
 pub fn range_color(
-    falloff: i16,
+    falloff: i32,
     base: image::Rgba<u8>,
     base_geo: Coordinate,
     to_geo: Coordinate,
@@ -116,7 +117,7 @@ pub fn random_item(list: &[String]) -> &String {
  ```
  */
 pub fn border(a: u8, b: i32) -> u8 {
-    max(min(i32::from(a) + b, 255 as i32), 0) as u8
+    (i32::from(a) + b).clamp(0, 255) as u8
 }
 
 /**
@@ -133,7 +134,7 @@ println!("{:?}", rgba.data);
 */
 pub fn gen_rgba() -> Rgba<u8> {
     (0..4).fold(super::consts::DEFAULT_RGBA, |mut acc, x| {
-        acc.data[x] = acc.data[x].saturating_add(roll(0u8, u8::max_value()) as u8);
+        acc.data[x] = acc.data[x].saturating_add(roll(0u8, u8::MAX) as u8);
         acc
     })
 }
@@ -191,7 +192,10 @@ pub fn plot_type(
 ) -> Vec<Coordinate> {
     // If any of the coordinates are negative, interally add to make them positive.
     if a.lt(0) || b.lt(0) {
-        let add = Coordinate::new(max(-a.x, -b.x), max(-a.y, -b.y));
+        let add = Coordinate::new(
+            max(a.x.saturating_neg(), b.x.saturating_neg()),
+            max(a.y.saturating_neg(), b.y.saturating_neg()),
+        );
         plot_type(a + add, b + add, &plot_bresenham)
             .iter()
             .fold(vec![], |mut acc, c| {
@@ -237,12 +241,12 @@ pub fn plot_bresenham(mut from: Coordinate, to: Coordinate) -> Vec<Coordinate> {
 
     for x in min(from.x, to.x)..=max(from.x, to.x) {
         for y in min(last_y, from.y)..=max(last_y, from.y) {
-            plot.push(Coordinate::new(x as i16, y));
+            plot.push(Coordinate::new(x, y));
         }
         last_y = from.y;
         error += delta_err;
         while error >= 0.50 {
-            from.y += f64::signum(delta_y) as i16;
+            from.y += f64::signum(delta_y) as i32;
             error -= 1.00;
         }
     }
@@ -307,12 +311,36 @@ Derived from: https://en.wikipedia.org/wiki/Ellipse
 
 This functionality is heavily a work in progress and it's behaviour is unreliable.
 */
-pub fn plot_ellipse(mut from: Coordinate, to: Coordinate) -> Vec<Coordinate> {
+pub fn plot_ellipse(from: Coordinate, to: Coordinate) -> Vec<Coordinate> {
+    ellipse_points(from, to)
+        .windows(2)
+        .fold(vec![], |mut acc, w| {
+            acc.append(&mut plot_type(w[0], w[1], &plot_bresenham));
+            acc
+        })
+}
+
+/**
+Returns the corner points of the ellipse drawn by plot_ellipse, starting with from and ending with to.
+
+
+## Examples
+
+```
+# use pathtracer::{tools, Coordinate};
+let a = Coordinate::new(0, 0);
+let b = Coordinate::new(100, 50);
+let points = tools::ellipse_points(a, b);
+assert_eq!(points.first(), Some(&a));
+assert_eq!(points.last(), Some(&b));
+```
+*/
+pub fn ellipse_points(mut from: Coordinate, to: Coordinate) -> Vec<Coordinate> {
     let min = Coordinate::new(min(from.x, to.x), min(from.y, to.y));
     let _max = Coordinate::new(max(from.x, to.x), max(from.y, to.y));
     let _t_max = midpoint(from, to);
 
-    let mut result = Vec::new();
+    let mut result = vec![from];
     let t = f64::consts::PI / 2.0; // How the ellipse is angled. and how steep it angles.
     let mut theta: f64 = 0.5; // starting angle.
     let diff = from - to;
@@ -334,16 +362,477 @@ pub fn plot_ellipse(mut from: Coordinate, to: Coordinate) -> Vec<Coordinate> {
     while t > theta {
         let point = from + coordinate!(r * theta.cos(), f64::from(s.y) * r * theta.sin() / 2.0);
         debug!("Theta: {} | Coordinate: {} <- {}", theta, point, from);
-        let mut line = plot_type(from, point, &plot_bresenham);
-        result.append(&mut line);
+        result.push(point);
         from = point;
         theta += step;
     }
-    result.append(&mut plot_type(from, to, &plot_bresenham));
-    debug!("{:#?}", result);
+    result.push(to);
     result
 }
 
+/**
+Draws a curve between two coordinate points that leaves and enters them horizontally.
+
+Uses a cubic Bezier curve with the control points from bezier_control.
+*/
+pub fn plot_bezier(from: Coordinate, to: Coordinate) -> Vec<Coordinate> {
+    let control = bezier_control(from, to);
+    let steps = bezier_steps(from, to);
+    bezier_points(&control, steps)
+        .windows(2)
+        .fold(vec![], |mut acc, w| {
+            acc.append(&mut plot(w[0], w[1]));
+            acc
+        })
+}
+
+/**
+Returns the control points for the curve drawn by plot_bezier.
+
+The two middle points are placed halfway between from and to horizontally, level with each end.
+
+
+## Examples
+
+```
+# use pathtracer::{tools, Coordinate};
+let control = tools::bezier_control(Coordinate::new(0, 0), Coordinate::new(100, 50));
+assert_eq!(control.to_vec(), Coordinate::from_list(&[(0, 0), (50, 0), (50, 50), (100, 50)]));
+```
+*/
+pub fn bezier_control(from: Coordinate, to: Coordinate) -> [Coordinate; 4] {
+    let mid = midpoint(from, to);
+    [
+        from,
+        Coordinate::new(mid.x, from.y),
+        Coordinate::new(mid.x, to.y),
+        to,
+    ]
+}
+
+/**
+Returns the number of segments used to draw the curve between two points, About one for every four positions.
+*/
+pub fn bezier_steps(from: Coordinate, to: Coordinate) -> usize {
+    let delta = to - from;
+    let length = max(delta.x.abs(), delta.y.abs()) as usize;
+    (length / 4).clamp(2, 32)
+}
+
+/**
+Returns steps + 1 points along the Bezier curve through the control points, starting with the first and ending with the last.
+
+Three control points give a quadratic curve and four a cubic, Any number above one is supported.
+Derived from: https://en.wikipedia.org/wiki/De_Casteljau%27s_algorithm
+
+
+## Examples
+
+```
+# use pathtracer::{tools, Coordinate};
+let control = Coordinate::from_list(&[(0, 0), (50, 100), (100, 0)]);
+let points = tools::bezier_points(&control, 2);
+assert_eq!(points, Coordinate::from_list(&[(0, 0), (50, 50), (100, 0)]));
+```
+*/
+pub fn bezier_points(control: &[Coordinate], steps: usize) -> Vec<Coordinate> {
+    if control.len() < 2 || steps == 0 {
+        return control.to_vec();
+    }
+
+    (0..=steps)
+        .map(|i| {
+            let t = i as f64 / steps as f64;
+            let mut points: Vec<(f64, f64)> = control
+                .iter()
+                .map(|c| (f64::from(c.x), f64::from(c.y)))
+                .collect();
+            while points.len() > 1 {
+                points = points
+                    .windows(2)
+                    .map(|w| {
+                        (
+                            w[0].0 + (w[1].0 - w[0].0) * t,
+                            w[0].1 + (w[1].1 - w[0].1) * t,
+                        )
+                    })
+                    .collect();
+            }
+            Coordinate::new(points[0].0.round() as i32, points[0].1.round() as i32)
+        })
+        .collect()
+}
+
+/**
+Draws a line between two coordinate points using only horizontal and vertical segments.
+
+The line bends twice, turning vertical halfway between the points.
+*/
+pub fn plot_orthogonal(from: Coordinate, to: Coordinate) -> Vec<Coordinate> {
+    orthogonal_points(from, to)
+        .windows(2)
+        .fold(vec![from], |mut acc, w| {
+            acc.append(&mut plot_rectangle(w[0], w[1]));
+            acc
+        })
+}
+
+/**
+Returns the corner points of the line drawn by plot_orthogonal, starting with from and ending with to.
+
+Points that are already level with each other skip the bends.
+
+
+## Examples
+
+```
+# use pathtracer::{tools, Coordinate};
+let points = tools::orthogonal_points(Coordinate::new(0, 0), Coordinate::new(100, 50));
+assert_eq!(points, Coordinate::from_list(&[(0, 0), (50, 0), (50, 50), (100, 50)]));
+
+let points = tools::orthogonal_points(Coordinate::new(0, 0), Coordinate::new(100, 0));
+assert_eq!(points, Coordinate::from_list(&[(0, 0), (100, 0)]));
+```
+*/
+pub fn orthogonal_points(from: Coordinate, to: Coordinate) -> Vec<Coordinate> {
+    if from.x == to.x || from.y == to.y {
+        return vec![from, to];
+    }
+
+    let mid = midpoint(from, to);
+    vec![
+        from,
+        Coordinate::new(mid.x, from.y),
+        Coordinate::new(mid.x, to.y),
+        to,
+    ]
+}
+
+/**
+Rounds every point to the nearest whole position.
+
+
+## Examples
+
+```
+# use pathtracer::{tools, Coordinate, Point};
+let points = [Point::new(0.4, 1.6), Point::new(-2.5, 3.0)];
+assert_eq!(tools::round(&points), Coordinate::from_list(&[(0, 2), (-3, 3)]));
+```
+*/
+pub fn round(points: &[Point]) -> Vec<Coordinate> {
+    points.iter().map(|p| Coordinate::from(*p)).collect()
+}
+
+/**
+Removes repeated positions, keeping the first occurrence of each.
+
+Used before drawing, so transparent colors are not blended with themselves.
+
+
+## Examples
+
+```
+# use pathtracer::{tools, Coordinate};
+let points = Coordinate::from_list(&[(0, 0), (1, 1), (0, 0)]);
+assert_eq!(tools::unique(points), Coordinate::from_list(&[(0, 0), (1, 1)]));
+```
+*/
+pub fn unique(points: Vec<Coordinate>) -> Vec<Coordinate> {
+    let mut seen = HashSet::new();
+    points
+        .into_iter()
+        .filter(|c| seen.insert((c.x, c.y)))
+        .collect()
+}
+
+/**
+Merges repeated positions in a coverage list, keeping the highest coverage of each.
+*/
+pub fn unique_coverage(plot: Vec<(Coordinate, f64)>) -> Vec<(Coordinate, f64)> {
+    let mut index: HashMap<(i32, i32), usize> = HashMap::new();
+    let mut merged: Vec<(Coordinate, f64)> = Vec::new();
+    for (c, coverage) in plot {
+        match index.get(&(c.x, c.y)) {
+            Some(i) => merged[*i].1 = merged[*i].1.max(coverage),
+            None => {
+                index.insert((c.x, c.y), merged.len());
+                merged.push((c, coverage));
+            }
+        }
+    }
+    merged
+}
+
+/**
+Returns the positions inside the polygon, including its outline.
+
+The last point connects back to the first.
+
+
+## Examples
+
+```
+# use pathtracer::{tools, Coordinate};
+let square = Coordinate::from_list(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+let area = tools::polygon_area(&square);
+assert!(area.contains(&Coordinate::new(2, 2)));
+assert!(!area.contains(&Coordinate::new(5, 2)));
+```
+*/
+pub fn polygon_area(points: &[Coordinate]) -> Vec<Coordinate> {
+    let mut area = Vec::new();
+    let edges = points.iter().zip(points.iter().cycle().skip(1));
+    for (a, b) in edges.clone() {
+        area.append(&mut plot(*a, *b));
+    }
+
+    let min_y = points.iter().map(|p| p.y).min().unwrap_or(0);
+    let max_y = points.iter().map(|p| p.y).max().unwrap_or(0);
+    for y in min_y..=max_y {
+        // Sample the middle of the row, so no row crosses a point exactly.
+        let row = f64::from(y) + 0.5;
+        let mut crossings = edges
+            .clone()
+            .filter(|(a, b)| (f64::from(a.y) <= row) != (f64::from(b.y) <= row))
+            .map(|(a, b)| {
+                let t = (row - f64::from(a.y)) / f64::from(b.y - a.y);
+                f64::from(a.x) + t * f64::from(b.x - a.x)
+            })
+            .collect::<Vec<_>>();
+        crossings.sort_by(|a, b| a.total_cmp(b));
+        for pair in crossings.chunks_exact(2) {
+            for x in pair[0].round() as i32..=pair[1].round() as i32 {
+                area.push(Coordinate::new(x, y));
+            }
+        }
+    }
+    unique(area)
+}
+
+/**
+Returns the point halfway along the path through all the points.
+
+
+## Examples
+
+```
+# use pathtracer::{tools, Coordinate};
+let path = Coordinate::from_list(&[(0, 0), (10, 0), (10, 10)]);
+assert_eq!(tools::halfway(&path), Coordinate::new(10, 0));
+```
+*/
+pub fn halfway(points: &[Coordinate]) -> Coordinate {
+    let length = |a: &Coordinate, b: &Coordinate| f64::from(a.x - b.x).hypot(f64::from(a.y - b.y));
+    let total = points.windows(2).map(|w| length(&w[0], &w[1])).sum::<f64>();
+
+    let mut remaining = total / 2.0;
+    for w in points.windows(2) {
+        let l = length(&w[0], &w[1]);
+        if l >= remaining && l > 0.0 {
+            let t = remaining / l;
+            return coordinate!(
+                f64::from(w[0].x) + t * f64::from(w[1].x - w[0].x),
+                f64::from(w[0].y) + t * f64::from(w[1].y - w[0].y)
+            );
+        }
+        remaining -= l;
+    }
+    points.first().copied().unwrap_or_default()
+}
+
+/**
+Draws an anti-aliased line between two coordinate points, Each position is paired with how much of it the line covers from 0.0 to 1.0.
+
+Derived from: https://en.wikipedia.org/wiki/Xiaolin_Wu%27s_line_algorithm
+
+
+## Examples
+
+```
+# use pathtracer::{tools, Coordinate};
+let plot = tools::plot_wu(Coordinate::new(0, 0), Coordinate::new(10, 5));
+assert!(plot.iter().all(|(_, coverage)| *coverage > 0.0 && *coverage <= 1.0));
+assert!(plot.contains(&(Coordinate::new(10, 5), 1.0)));
+```
+*/
+pub fn plot_wu(a: Coordinate, b: Coordinate) -> Vec<(Coordinate, f64)> {
+    let (mut x0, mut y0) = (a.x, a.y);
+    let (mut x1, mut y1) = (b.x, b.y);
+    let steep = (y1 - y0).abs() > (x1 - x0).abs();
+    if steep {
+        swap(&mut x0, &mut y0);
+        swap(&mut x1, &mut y1);
+    }
+    if x0 > x1 {
+        swap(&mut x0, &mut x1);
+        swap(&mut y0, &mut y1);
+    }
+
+    let (dx, dy) = (x1 - x0, y1 - y0);
+    let mut plot = Vec::new();
+    let mut push = |x: i32, y: i32, coverage: f64| {
+        if coverage > 0.0 {
+            let c = if steep {
+                coordinate!(y, x)
+            } else {
+                coordinate!(x, y)
+            };
+            plot.push((c, coverage));
+        }
+    };
+
+    // The endpoints are whole positions, so y is kept as an exact fraction of dx.
+    for x in x0..=x1 {
+        let (y, fract) = match dx {
+            0 => (y0, 0.0),
+            _ => {
+                // Widened so the product can not overflow for distant points.
+                let (n, d) = (
+                    i64::from(y0) * i64::from(dx) + i64::from(dy) * i64::from(x - x0),
+                    i64::from(dx),
+                );
+                (n.div_euclid(d) as i32, n.rem_euclid(d) as f64 / d as f64)
+            }
+        };
+        push(x, y, 1.0 - fract);
+        push(x, y + 1, fract);
+    }
+    plot
+}
+
+/**
+Returns the positions covered by a line of the given width between two points, paired with their coverage from 0.0 to 1.0.
+*/
+pub fn line_coverage(a: Coordinate, b: Coordinate, width: f64) -> Vec<(Coordinate, f64)> {
+    let reach = width / 2.0 + 0.5;
+    let (ax, ay) = (f64::from(a.x), f64::from(a.y));
+    let (dx, dy) = (f64::from(b.x) - ax, f64::from(b.y) - ay);
+    let length = dx * dx + dy * dy;
+
+    let pad = reach.ceil() as i32;
+    let mut plot = Vec::new();
+    for y in min(a.y, b.y) - pad..=max(a.y, b.y) + pad {
+        for x in min(a.x, b.x) - pad..=max(a.x, b.x) + pad {
+            let (px, py) = (f64::from(x) - ax, f64::from(y) - ay);
+            let t = if length == 0.0 {
+                0.0
+            } else {
+                ((px * dx + py * dy) / length).clamp(0.0, 1.0)
+            };
+            let distance = (px - t * dx).hypot(py - t * dy);
+            let coverage = (reach - distance).min(1.0);
+            if coverage > 0.0 {
+                plot.push((Coordinate::new(x, y), coverage));
+            }
+        }
+    }
+    plot
+}
+
+/**
+Returns the positions covered by a circle around the origin, paired with their coverage from 0.0 to 1.0.
+
+The edge is blended over one position, so pixels up to radius away are covered.
+*/
+pub fn circle_coverage(radius: f64) -> Vec<(Coordinate, f64)> {
+    let r = radius.ceil() as i32;
+    let mut plot = Vec::new();
+    for y in -r..=r {
+        for x in -r..=r {
+            let coverage = (radius + 0.5 - f64::from(x).hypot(f64::from(y))).min(1.0);
+            if coverage > 0.0 {
+                plot.push((Coordinate::new(x, y), coverage));
+            }
+        }
+    }
+    plot
+}
+
+/**
+Returns the positions covered by the outline of a circle around the origin, paired with their coverage from 0.0 to 1.0.
+
+The outline is width positions wide inside the radius, Both edges are blended over one position.
+
+
+## Examples
+
+```
+# use pathtracer::{tools, Coordinate};
+let ring = tools::ring_coverage(4.0, 1.0);
+assert!(ring.iter().any(|(c, _)| *c == Coordinate::new(4, 0)));
+assert!(ring.iter().all(|(c, _)| *c != Coordinate::new(0, 0)));
+```
+*/
+pub fn ring_coverage(radius: f64, width: f64) -> Vec<(Coordinate, f64)> {
+    let inner = radius - width;
+    circle_coverage(radius)
+        .into_iter()
+        .filter_map(|(c, coverage)| {
+            let distance = f64::from(c.x).hypot(f64::from(c.y));
+            let coverage = coverage.min(distance - inner + 0.5);
+            if coverage > 0.0 {
+                Some((c, coverage.min(1.0)))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/**
+Returns the positions covered by the polygon, paired with their coverage from 0.0 to 1.0.
+
+Coverage is estimated by sampling each position in a 4x4 grid, The last point connects back to the first.
+*/
+pub fn polygon_coverage(points: &[Coordinate]) -> Vec<(Coordinate, f64)> {
+    const SAMPLES: i32 = 4;
+    let inside = |x: f64, y: f64| {
+        let edges = points.iter().zip(points.iter().cycle().skip(1));
+        edges
+            .filter(|(a, b)| {
+                let (ay, by) = (f64::from(a.y), f64::from(b.y));
+                (ay <= y) != (by <= y)
+                    && x < f64::from(a.x) + (y - ay) / (by - ay) * f64::from(b.x - a.x)
+            })
+            .count()
+            % 2
+            == 1
+    };
+
+    let mut plot = Vec::new();
+    let (min_x, max_x) = match (
+        points.iter().map(|p| p.x).min(),
+        points.iter().map(|p| p.x).max(),
+    ) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return plot,
+    };
+    let min_y = points.iter().map(|p| p.y).min().unwrap_or(0);
+    let max_y = points.iter().map(|p| p.y).max().unwrap_or(0);
+
+    for y in min_y - 1..=max_y {
+        for x in min_x - 1..=max_x {
+            let mut count = 0;
+            for i in 0..SAMPLES {
+                for j in 0..SAMPLES {
+                    // Samples are spread over the position, which is centred on x + 0.5.
+                    let sx = f64::from(x) + (f64::from(j) + 0.5) / f64::from(SAMPLES);
+                    let sy = f64::from(y) + (f64::from(i) + 0.5) / f64::from(SAMPLES);
+                    if inside(sx, sy) {
+                        count += 1;
+                    }
+                }
+            }
+            if count > 0 {
+                let coverage = f64::from(count) / f64::from(SAMPLES * SAMPLES);
+                plot.push((Coordinate::new(x, y), coverage));
+            }
+        }
+    }
+    plot
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -405,13 +894,123 @@ mod tests {
         assert!(c1 == plot[0] && c2 == plot[plot.len() - 1]);
     }
 
+    #[test]
+    fn test_bezier_points() {
+        let control = Coordinate::from_list(&[(0, 0), (10, 0), (10, 10), (20, 10)]);
+        let points = bezier_points(&control, 4);
+        assert_eq!(points.len(), 5);
+        assert_eq!(points[0], control[0]);
+        assert_eq!(points[2], Coordinate::new(10, 5));
+        assert_eq!(points[4], control[3]);
+        assert_eq!(bezier_points(&control[..1], 4), vec![control[0]]);
+    }
+
+    #[test]
+    fn test_plot_bezier_and_orthogonal() {
+        let a = Coordinate::new(0, 0);
+        let b = Coordinate::new(40, 20);
+        for plot in &[plot_bezier(a, b), plot_orthogonal(a, b)] {
+            assert!(plot.contains(&a));
+            assert!(plot.contains(&b));
+            assert!(plot.iter().all(|c| c.x >= a.x && c.x <= b.x));
+            assert!(plot.iter().all(|c| c.y >= a.y && c.y <= b.y));
+        }
+        let orthogonal = plot_orthogonal(a, b);
+        assert!(orthogonal
+            .windows(2)
+            .all(|w| w[0].x == w[1].x || w[0].y == w[1].y));
+    }
+
+    #[test]
+    fn test_polygon_area_triangle() {
+        let triangle = Coordinate::from_list(&[(0, 0), (10, 5), (0, 10)]);
+        let area = polygon_area(&triangle);
+        assert!(area.contains(&Coordinate::new(2, 5)));
+        assert!(area.contains(&Coordinate::new(10, 5)));
+        assert!(!area.contains(&Coordinate::new(8, 1)));
+        assert!(polygon_area(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_halfway() {
+        let c = Coordinate::new(3, 4);
+        assert_eq!(halfway(&[c]), c);
+        assert_eq!(halfway(&[c, c]), c);
+        assert_eq!(
+            halfway(&[Coordinate::new(0, 0), Coordinate::new(10, 20)]),
+            Coordinate::new(5, 10)
+        );
+    }
+
+    #[test]
+    fn test_plot_beyond_i16() {
+        let a = Coordinate::new(-40_000, 70_000);
+        let b = Coordinate::new(40_000, 70_010);
+        let plot = plot(a, b);
+        assert!(plot.contains(&a) && plot.contains(&b));
+        let wu = plot_wu(a, b);
+        assert!(wu.contains(&(a, 1.0)) && wu.contains(&(b, 1.0)));
+    }
+
+    #[test]
+    fn test_plot_wu() {
+        let plot = plot_wu(Coordinate::new(0, 0), Coordinate::new(10, 0));
+        assert_eq!(plot.len(), 11);
+        assert!(plot
+            .iter()
+            .all(|(c, coverage)| c.y == 0 && *coverage == 1.0));
+
+        let plot = plot_wu(Coordinate::new(0, 10), Coordinate::new(4, 0));
+        assert!(plot.contains(&(Coordinate::new(0, 10), 1.0)));
+        assert!(plot.contains(&(Coordinate::new(4, 0), 1.0)));
+        for y in 0..=10 {
+            let total: f64 = plot.iter().filter(|(c, _)| c.y == y).map(|x| x.1).sum();
+            assert!((total - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_line_coverage() {
+        let plot = line_coverage(Coordinate::new(0, 0), Coordinate::new(10, 0), 2.0);
+        let at = |x, y| {
+            plot.iter()
+                .find(|(c, _)| *c == Coordinate::new(x, y))
+                .map_or(0.0, |x| x.1)
+        };
+        assert_eq!(at(5, 0), 1.0);
+        assert_eq!(at(5, 1), 0.5);
+        assert_eq!(at(5, 2), 0.0);
+        assert_eq!(at(11, 0), 0.5);
+    }
+
+    #[test]
+    fn test_circle_coverage() {
+        let plot = circle_coverage(3.0);
+        assert!(plot.contains(&(Coordinate::new(0, 0), 1.0)));
+        assert!(plot.contains(&(Coordinate::new(3, 0), 0.5)));
+        assert!(plot.iter().all(|(c, _)| c.x.abs() <= 3 && c.y.abs() <= 3));
+    }
+
+    #[test]
+    fn test_polygon_coverage() {
+        let square = Coordinate::from_list(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+        let plot = polygon_coverage(&square);
+        assert_eq!(plot.len(), 16);
+        assert!(plot.iter().all(|(_, coverage)| *coverage == 1.0));
+
+        let triangle = Coordinate::from_list(&[(0, 0), (4, 0), (0, 4)]);
+        let plot = polygon_coverage(&triangle);
+        assert!(plot.iter().any(|(_, coverage)| *coverage < 1.0));
+        assert!(polygon_coverage(&[]).is_empty());
+    }
+
     #[test]
     fn test_plot_straight_line() {
         let c1 = Coordinate::new(0, 0);
         let c2 = Coordinate::new(0, 10);
         let plot = plot(c1, c2);
-        for i in 0..10 {
-            assert_eq!(plot[i].y, i as i16);
+        for (i, c) in plot.iter().enumerate() {
+            assert_eq!(c.y, i as i32);
         }
     }
 }
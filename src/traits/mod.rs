@@ -5,12 +5,250 @@ Collection of traits.
 use super::*;
 
 /**
-Functions required to draw the structure on the image.
+Functions required to draw the structure on a Canvas.
+
+The offset is a position in world space, so structures are moved with sub-pixel precision.
  */
 pub trait Draw {
-    fn draw(&self, image: IW, offset: Coordinate, shape: &Shape) -> IW;
+    fn draw<C: Canvas>(&self, canvas: C, offset: Point, shape: &Shape) -> C;
     fn size(&self) -> u32;
     fn links(&self) -> &[HL];
+
+    /**
+    Draws the text labels of the structure, Draws nothing by default.
+     */
+    fn draw_label<C: Canvas>(&self, canvas: C, _offset: Point) -> C {
+        canvas
+    }
+}
+
+/**
+Functions required to position the structure with the layout module.
+
+Links from the structure, or from the Nodes it contains, pull it towards the structures they point at.
+ */
+pub trait Layout: Hash + Location {
+    /**
+    Returns the hashes of everything the structure links to.
+     */
+    fn targets(&self) -> Vec<u64>;
+
+    /**
+    Returns the hashes links may point at in the structure, along with their positions.
+     */
+    fn ends(&self) -> Vec<(u64, Point)>;
+
+    /**
+    Moves the structure to the position in world space.
+     */
+    fn place(&mut self, point: Point);
+
+    /**
+    Moves the ends of the links in the structure to the positions of what they point at.
+
+    Links pointing at hashes which are not found keep their current end.
+     */
+    fn relink(&mut self, ends: &std::collections::HashMap<u64, Point>);
+
+    /**
+    Sets the positions in world space that links in the structure to any of the hashes bend at.
+     */
+    fn route(&mut self, to: &[u64], via: &[Point]);
+}
+
+/**
+A surface that structures implementing Draw are rendered on to.
+
+Only blank and put are required, the other functions are built on top of put.
+Shapes, lines and text are positioned with sub-pixel precision, and rounded to whole positions when drawn.
+Backends that can draw lines and shapes natively, such as Svg, override them.
+
+When anti-aliased, lines and shapes are drawn using blend with the share of each position they cover.
+
+
+## Examples
+
+```
+# use pathtracer::*;
+struct Count(usize);
+
+impl Canvas for Count {
+    fn blank(_: u32, _: u32) -> Self {
+        Count(0)
+    }
+
+    fn put(&mut self, _: Coordinate, _: image::Rgba<u8>) {
+        self.0 += 1;
+    }
+}
+
+let nodes = Node::from_list(&[(0, 0), (10, 10)]);
+let count = Map::<Count>::empty().map(&nodes).consume();
+assert!(count.0 > 0);
+```
+ */
+pub trait Canvas {
+    /**
+    Constructs an empty canvas with the given width and height.
+     */
+    fn blank(width: u32, height: u32) -> Self
+    where
+        Self: Sized;
+
+    /**
+    Colors a single position.
+     */
+    fn put(&mut self, pos: Coordinate, color: image::Rgba<u8>);
+
+    /**
+    Mixes the color in to the existing color at pos, coverage from 0.0 to 1.0 is the share of the position that is covered.
+
+    Puts the color if at least half the position is covered by default.
+     */
+    fn blend(&mut self, pos: Coordinate, color: image::Rgba<u8>, coverage: f64) {
+        if coverage >= 0.5 {
+            self.put(pos, color);
+        }
+    }
+
+    /**
+    Returns true if lines and shapes are drawn anti-aliased.
+     */
+    fn anti_aliased(&self) -> bool {
+        false
+    }
+
+    /**
+    Enables or disables drawing anti-aliased lines and shapes, Ignored by default.
+     */
+    fn set_anti_aliased(&mut self, _enabled: bool) {}
+
+    /**
+    Sets how colors are combined with what is already drawn, Ignored by default.
+     */
+    fn set_blend_mode(&mut self, _mode: BlendMode) {}
+
+    /**
+    Draws a line through all the points, width positions wide.
+
+    Anti-aliased lines use Xiaolin Wu's algorithm when one position wide, and the distance to the line when wider.
+     */
+    fn line(&mut self, points: &[Point], color: image::Rgba<u8>, width: u32) {
+        let points = tools::round(points);
+        if self.anti_aliased() {
+            let plot = points.windows(2).fold(vec![], |mut acc, w| {
+                acc.append(&mut match width {
+                    0 => Vec::new(),
+                    1 => tools::plot_wu(w[0], w[1]),
+                    _ => tools::line_coverage(w[0], w[1], f64::from(width)),
+                });
+                acc
+            });
+            for (c, coverage) in tools::unique_coverage(plot) {
+                self.blend(c, color, coverage);
+            }
+            return;
+        }
+
+        let s = coordinate!(width / 2);
+        let mut plot = Vec::new();
+        for i in 0..width {
+            for j in 0..width {
+                let add = coordinate!(j, i) - s;
+                for w in points.windows(2) {
+                    plot.append(&mut tools::plot(w[0] + add, w[1] + add));
+                }
+            }
+        }
+        for c in tools::unique(plot) {
+            self.put(c, color);
+        }
+    }
+
+    /**
+    Fills the shape with its top-left corner at pos.
+     */
+    fn fill(&mut self, pos: Point, shape: &Shape, size: u32, color: image::Rgba<u8>) {
+        let pos = Coordinate::from(pos);
+        if self.anti_aliased() {
+            for (c, coverage) in shape.coverage(size as usize) {
+                self.blend(pos + c, color, coverage);
+            }
+            return;
+        }
+
+        for o in tools::unique(shape.area(size as usize)) {
+            self.put(pos + o, color);
+        }
+    }
+
+    /**
+    Draws the outline of the shape with its top-left corner at pos, width positions wide inside the shape.
+
+    Anti-aliased outlines keep the blended outer edge of the filled shape.
+     */
+    fn stroke(&mut self, pos: Point, shape: &Shape, size: u32, color: image::Rgba<u8>, width: u32) {
+        let pos = Coordinate::from(pos);
+        if self.anti_aliased() && *shape == Shape::Circle {
+            let radius = Shape::radius(size as usize) as f64;
+            let ring = tools::ring_coverage(radius, f64::from(width));
+            for (c, coverage) in ring {
+                self.blend(pos + c, color, coverage);
+            }
+            return;
+        }
+
+        let outline = shape.outline(size as usize, width);
+        if self.anti_aliased() {
+            let edge: std::collections::HashSet<(i32, i32)> =
+                outline.iter().map(|c| (c.x, c.y)).collect();
+            let inside: std::collections::HashSet<(i32, i32)> = shape
+                .area(size as usize)
+                .into_iter()
+                .map(|c| (c.x, c.y))
+                .filter(|c| !edge.contains(c))
+                .collect();
+            for (c, coverage) in shape.coverage(size as usize) {
+                if width > 0 && !inside.contains(&(c.x, c.y)) {
+                    self.blend(pos + c, color, coverage);
+                }
+            }
+            return;
+        }
+
+        for o in outline {
+            self.put(pos + o, color);
+        }
+    }
+
+    /**
+    Fills the polygon through the points, The last point connects back to the first.
+     */
+    fn polygon(&mut self, points: &[Point], color: image::Rgba<u8>) {
+        let points = tools::round(points);
+        if self.anti_aliased() {
+            for (c, coverage) in tools::polygon_coverage(&points) {
+                self.blend(c, color, coverage);
+            }
+            return;
+        }
+
+        for c in tools::polygon_area(&points) {
+            self.put(c, color);
+        }
+    }
+
+    /**
+    Writes the text with its top-left corner at pos.
+
+    Uses the embedded bitmap font in map::font by default.
+     */
+    fn text(&mut self, pos: Point, text: &str, color: image::Rgba<u8>) {
+        let pos = Coordinate::from(pos);
+        for c in map::font::text_points(text) {
+            self.put(pos + c, color);
+        }
+    }
 }
 
 /**
@@ -22,6 +260,20 @@ pub trait Location {
      */
     fn position(&self) -> Coordinate;
 
+    /**
+    Retrieves the position in world space, The whole position unless the structure keeps a finer one.
+     */
+    fn world(&self) -> Point {
+        Point::from(self.position())
+    }
+
+    /**
+    Returns if the position was created from a latitude and longitude, See Node::from_lat_lon.
+     */
+    fn geographic(&self) -> bool {
+        false
+    }
+
     /**
     Returns if the positions are equal or not.
      */
@@ -32,22 +284,22 @@ pub trait Location {
     /**
     Retrieves the X coordinate.
      */
-    fn x(&self) -> i16 {
+    fn x(&self) -> i32 {
         self.position().x
     }
 
     /**
     Retrieves the Y coordinate.
      */
-    fn y(&self) -> i16 {
+    fn y(&self) -> i32 {
         self.position().y
     }
 
     /**
     Returns the sum of the x and y value.
      */
-    fn sum(&self) -> i16 {
-        self.x() + self.y()
+    fn sum(&self) -> i32 {
+        self.x().saturating_add(self.y())
     }
 }
 
@@ -75,4 +327,11 @@ pub trait Find: Hash + Location {
 
 pub trait Hash {
     fn hash(&self) -> u64;
+
+    /**
+    Retrieves the name the hash was calculated from, if it was kept.
+     */
+    fn name(&self) -> Option<&str> {
+        None
+    }
 }